@@ -4,15 +4,16 @@ use bevy_mouse_tracking_plugin::{
     mouse_pos::InitMouseTracking, prelude::*, MainCamera, MouseMotion, MousePos,
 };
 
-#[derive(Component)]
-struct Cursor;
-
 #[derive(Component)]
 struct Hud;
 
 fn main() {
     App::new()
-        .add_plugins((DefaultPlugins, MousePosPlugin, MouseMotionPlugin))
+        .add_plugins((
+            DefaultPlugins,
+            MousePosPlugin::default(),
+            MouseMotionPlugin::default(),
+        ))
         .insert_resource(ClearColor(Color::BLACK))
         .add_systems(Startup, setup)
         .add_systems(Update, bevy::window::close_on_esc)
@@ -47,7 +48,7 @@ fn setup(
         color: Color::ORANGE,
     };
     let (win_width, win_height) = (window.width(), window.height());
-    let (hud_x, hud_y) = (win_width / 2. * -1., win_height / 2.);
+    let (hud_x, hud_y) = (-(win_width / 2.), win_height / 2.);
     let translation = Vec3::new(hud_x, hud_y, 0.);
     let transform = Transform::from_translation(translation);
     let value = "Mouse: (-, -)".to_string();
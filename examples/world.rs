@@ -1,18 +1,19 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 
 use bevy_mouse_tracking_plugin::{
-    mouse_pos::InitWorldTracking, prelude::*, MainCamera, MousePos, MousePosWorld,
+    mouse_pos::InitWorldTracking, prelude::*, CursorSprite, MainCamera, MousePos, MousePosWorld,
 };
 
-#[derive(Component)]
-struct Cursor;
-
 #[derive(Component)]
 struct Hud;
 
 fn main() {
     App::new()
-        .add_plugins((DefaultPlugins, MousePosPlugin))
+        .add_plugins((
+            DefaultPlugins,
+            MousePosPlugin::default(),
+            CursorSpritePlugin,
+        ))
         .insert_resource(ClearColor(Color::BLACK))
         .add_systems(Startup, setup)
         .add_systems(Update, bevy::window::close_on_esc)
@@ -31,7 +32,15 @@ fn setup(
     let mut camera_bundle = Camera2dBundle::default();
     camera_bundle.projection.scale = 0.5; // works fine with non-unit scaling.
     commands
-        .spawn((camera_bundle, MainCamera))
+        .spawn((
+            camera_bundle,
+            MainCamera,
+            // Draw the cursor as a sprite, centered on the mouse position.
+            CursorSprite {
+                image: asset_server.load("cursor.png"),
+                hotspot: Vec2::splat(32.0),
+            },
+        ))
         .add(InitWorldTracking);
 
     // Reference for the origin
@@ -40,15 +49,6 @@ fn setup(
         ..Default::default()
     });
 
-    // Reference for the mouse position
-    commands.spawn((
-        SpriteBundle {
-            texture: asset_server.load("cursor.png"),
-            ..Default::default()
-        },
-        Cursor,
-    ));
-
     // Hud
     let font = asset_server.load("FiraMono-Medium.ttf");
     let style = TextStyle {
@@ -57,7 +57,7 @@ fn setup(
         color: Color::ORANGE,
     };
     let (win_width, win_height) = (window.width(), window.height());
-    let (hud_x, hud_y) = (win_width / 2. * -1., win_height / 2.);
+    let (hud_x, hud_y) = (-(win_width / 2.), win_height / 2.);
     let translation = Vec3::new(hud_x, hud_y, 0.);
     let transform = Transform::from_translation(translation);
     let value = "Screen: (-, -)\nWorld: (-, -)".to_string();
@@ -93,7 +93,6 @@ fn run(
     mouse_screen_pos: Res<MousePos>,
     mouse_world_pos: Res<MousePosWorld>,
     mut hud_text: Query<&mut Text, With<Hud>>,
-    mut cursor: Query<&mut Transform, With<Cursor>>,
 ) {
     let hud_value = format!(
         "Screen: ({}, {})\nWorld: ({}, {})",
@@ -103,8 +102,4 @@ fn run(
     if let Some(mut hud_text) = hud_text.iter_mut().next() {
         hud_text.sections.first_mut().unwrap().value = hud_value;
     }
-
-    if let Some(mut cursor_transform) = cursor.iter_mut().next() {
-        cursor_transform.translation = Vec3::new(mouse_world_pos.x, mouse_world_pos.y, 0.);
-    }
 }
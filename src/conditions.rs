@@ -0,0 +1,77 @@
+//! [Run conditions](bevy::ecs::schedule::Condition) for systems that only need to run when the mouse moves.
+//!
+//! The tracking components and resources in this crate only get mutated when their value actually changes,
+//! so these conditions will not be fooled by the cursor staying in the same place.
+//!
+//! ```
+//! use bevy::{ecs::system::EntityCommand, prelude::*, window::CursorMoved};
+//! use bevy_mouse_tracking_plugin::{prelude::*, MainCamera};
+//!
+//! #[derive(Resource, Default, PartialEq, Debug)]
+//! struct Runs {
+//!     main: u32,
+//!     camera: u32,
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins((MinimalPlugins, WindowPlugin::default(), MousePosPlugin::default()))
+//!     .init_resource::<Runs>();
+//!
+//! let window = app.world.query_filtered::<Entity, With<Window>>().single(&app.world);
+//! let camera = app.world.spawn((Camera2dBundle::default(), MainCamera)).id();
+//! InitMouseTracking.apply(camera, &mut app.world);
+//!
+//! app.add_systems(
+//!     PostUpdate,
+//!     (
+//!         (|mut runs: ResMut<Runs>| runs.main += 1).run_if(cursor_moved()),
+//!         (|mut runs: ResMut<Runs>| runs.camera += 1).run_if(cursor_moved_in(camera)),
+//!     ),
+//! );
+//! app.update();
+//! app.world.insert_resource(Runs::default());
+//!
+//! // Moving the mouse causes the systems to run.
+//! app.world.send_event(CursorMoved { window, position: Vec2::new(10.0, 20.0) });
+//! app.update();
+//! assert_eq!(*app.world.resource::<Runs>(), Runs { main: 1, camera: 1 });
+//!
+//! // If the cursor doesn't go anywhere, the systems won't run.
+//! app.world.send_event(CursorMoved { window, position: Vec2::new(10.0, 20.0) });
+//! app.update();
+//! app.update();
+//! assert_eq!(*app.world.resource::<Runs>(), Runs { main: 1, camera: 1 });
+//! ```
+
+use bevy::prelude::*;
+
+use crate::{MouseMotion, MousePos, MousePosWorld};
+
+/// Run condition that is `true` if the mouse has moved within the [`MainCamera`](struct@crate::MainCamera) since the last time the condition was checked.
+///
+/// This is based on change detection of the [`MousePos`] resource, so it will also be `true` the first time it runs.
+pub fn cursor_moved() -> impl FnMut(Res<MousePos>) -> bool + Clone {
+    |pos: Res<MousePos>| pos.is_changed()
+}
+
+/// Run condition that is `true` if the mouse has moved within the specified camera since the last time the condition was checked.
+///
+/// This is `false` if the camera does not exist or does not have the [`MousePos`] component.
+pub fn cursor_moved_in(camera: Entity) -> impl FnMut(Query<Ref<MousePos>>) -> bool + Clone {
+    move |pos: Query<Ref<MousePos>>| pos.get(camera).is_ok_and(|pos| pos.is_changed())
+}
+
+/// Run condition that is `true` if the world-space position of the mouse within the [`MainCamera`](struct@crate::MainCamera)
+/// has changed since the last time the condition was checked.
+///
+/// This also accounts for changes caused by moving the camera, rather than the mouse.
+pub fn cursor_world_moved() -> impl FnMut(Res<MousePosWorld>) -> bool + Clone {
+    |pos: Res<MousePosWorld>| pos.is_changed()
+}
+
+/// Run condition that is `true` if the mouse moved at all during the current frame.
+///
+/// This requires the [`MouseMotionPlugin`](crate::mouse_motion::MouseMotionPlugin).
+pub fn mouse_motion_nonzero() -> impl FnMut(Res<MouseMotion>) -> bool + Clone {
+    |motion: Res<MouseMotion>| motion.delta != Vec2::ZERO
+}
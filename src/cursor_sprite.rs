@@ -0,0 +1,206 @@
+//! A software cursor, drawn as a sprite that follows the mouse.
+//!
+//! Requires the `sprite` feature.
+//! After adding [`CursorSpritePlugin`], add a [`CursorSprite`] component to any camera
+//! that tracks the mouse with [`InitMouseTracking`](crate::mouse_pos::InitMouseTracking).
+//! A sprite will be spawned for that camera, and the OS cursor will be hidden in the camera's window.
+//! Once the last sprite in a window goes away, the OS cursor is shown again.
+//! While the [`CursorLock`] is locked, the sprite in the primary window is hidden as well.
+//!
+//! The sprite is positioned after the camera's transform has been propagated,
+//! so it stays glued to the cursor even while the camera is moving.
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{CameraUpdateSystem, NormalizedRenderTarget},
+        view::{RenderLayers, VisibilitySystems},
+    },
+    sprite::Anchor,
+    transform::TransformSystem,
+    utils::HashSet,
+    window::PrimaryWindow,
+};
+
+use crate::{
+    mouse_motion::CursorLock,
+    mouse_pos::{compute_world_pos_ortho, pixel_size_ortho},
+    picking::IgnorePicking,
+    snapping::PixelSnapping,
+    MousePos,
+};
+
+/// How far in front of the camera's near plane the cursor is drawn, in the camera's local units.
+const CURSOR_DEPTH: f32 = 0.1;
+
+/// Plugin that draws a sprite in place of the OS cursor, for every camera with a [`CursorSprite`].
+pub struct CursorSpritePlugin;
+
+impl Plugin for CursorSpritePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_cursor_sprites
+                .after(TransformSystem::TransformPropagate)
+                .after(CameraUpdateSystem)
+                .before(VisibilitySystems::VisibilityPropagate)
+                .before(VisibilitySystems::CheckVisibility),
+        );
+    }
+}
+
+/// Component that replaces the OS cursor with a sprite, for the camera it is added to.
+///
+/// The camera must use an [`OrthographicProjection`] and be tracking the mouse.
+#[derive(Debug, Default, Clone, Component)]
+pub struct CursorSprite {
+    /// The image to draw as the cursor. It is drawn with one texel per logical pixel.
+    pub image: Handle<Image>,
+    /// The point of the image that lines up with the mouse position,
+    /// in pixels from the top left corner of the image.
+    pub hotspot: Vec2,
+}
+
+/// Marker component for the sprite entities spawned by [`CursorSpritePlugin`].
+#[derive(Debug, Clone, Copy, Component)]
+pub struct SoftwareCursor {
+    camera: Entity,
+}
+
+impl SoftwareCursor {
+    /// The camera that this cursor is drawn for.
+    pub fn camera(&self) -> Entity {
+        self.camera
+    }
+}
+
+fn update_cursor_sprites(
+    mut commands: Commands,
+    cameras: Query<
+        (
+            Entity,
+            &CursorSprite,
+            &MousePos,
+            &Camera,
+            &GlobalTransform,
+            &OrthographicProjection,
+            Option<&PixelSnapping>,
+            Option<&RenderLayers>,
+        ),
+        Without<SoftwareCursor>,
+    >,
+    mut sprites: Query<(
+        Entity,
+        &SoftwareCursor,
+        &mut Handle<Image>,
+        &mut Transform,
+        &mut GlobalTransform,
+        &mut Visibility,
+    )>,
+    mut windows: Query<&mut Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    cursor_lock: Option<Res<CursorLock>>,
+    mut hidden_windows: Local<HashSet<Entity>>,
+) {
+    let primary_window = primary_window.get_single().ok();
+
+    let mut has_sprite = HashSet::new();
+    let mut in_use = HashSet::new();
+    for (entity, cursor, mut image, mut transform, mut global, mut visibility) in sprites.iter_mut()
+    {
+        let Ok((_, sprite, &mouse_pos, camera, &camera_transform, proj, snapping, _)) =
+            cameras.get(cursor.camera)
+        else {
+            // The camera is gone, or no longer wants a custom cursor.
+            commands.entity(entity).despawn();
+            continue;
+        };
+        has_sprite.insert(cursor.camera);
+
+        if *image != sprite.image {
+            *image = sprite.image.clone();
+        }
+
+        let window = match camera.target.normalize(primary_window) {
+            Some(NormalizedRenderTarget::Window(window)) => windows
+                .get_mut(window.entity())
+                .ok()
+                .map(|w| (window.entity(), w)),
+            _ => None,
+        };
+        let Some((window_id, mut window)) = window else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        in_use.insert(window_id);
+        // While the cursor is locked, the mouse position is frozen, so there's no cursor to draw.
+        let locked = cursor_lock.as_ref().is_some_and(|lock| lock.is_locked());
+        if locked && Some(window_id) == primary_window {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        // Only hide the OS cursor once, so the app can still show it on purpose.
+        if hidden_windows.insert(window_id) {
+            window.cursor.visible = false;
+        }
+        // Only draw the cursor while it is inside of the window.
+        if window.cursor_position().is_none() {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        visibility.set_if_neq(Visibility::Visible);
+
+        // Build the transform in the camera's local space, so the cursor stays upright on the screen
+        // and keeps the same size regardless of the camera's rotation and zoom.
+        let pixel_size = pixel_size_ortho(camera, proj);
+        let world = compute_world_pos_ortho(*mouse_pos, camera, camera_transform, proj, snapping);
+        let local = camera_transform
+            .affine()
+            .inverse()
+            .transform_point3(world)
+            .truncate();
+        let offset = Vec2::new(-sprite.hotspot.x, sprite.hotspot.y) * pixel_size;
+        let local_transform =
+            Transform::from_translation((local + offset).extend(-(proj.near + CURSOR_DEPTH)))
+                .with_scale(pixel_size.extend(1.0));
+
+        // Transform propagation has already run this frame, so update the global transform directly.
+        *global = camera_transform.mul_transform(local_transform);
+        *transform = global.compute_transform();
+    }
+
+    // Bring back the OS cursor for any window that no longer has a sprite.
+    hidden_windows.retain(|&window_id| {
+        if in_use.contains(&window_id) {
+            return true;
+        }
+        if let Ok(mut window) = windows.get_mut(window_id) {
+            window.cursor.visible = true;
+        }
+        false
+    });
+
+    for (camera, sprite, .., layers) in &cameras {
+        if has_sprite.contains(&camera) {
+            continue;
+        }
+        let mut entity = commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    anchor: Anchor::TopLeft,
+                    ..default()
+                },
+                texture: sprite.image.clone(),
+                // Stay hidden until the sprite gets positioned next frame.
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            SoftwareCursor { camera },
+            // The cursor is always under the mouse, so it shouldn't block anything else.
+            IgnorePicking,
+        ));
+        if let Some(&layers) = layers {
+            entity.insert(layers);
+        }
+    }
+}
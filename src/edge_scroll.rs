@@ -0,0 +1,178 @@
+//! Panning cameras by moving the mouse to the edge of the screen, as in RTS games.
+//!
+//! After adding [`EdgeScrollPlugin`], add an [`EdgeScroll`] component to any camera that tracks the mouse.
+//! While the mouse is near the edge of that camera's viewport, the camera will pan in that direction.
+//! Scrolling stops when the cursor leaves the window, or when the window loses focus.
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_mouse_tracking_plugin::{edge_scroll::EdgeScroll, mouse_pos::InitMouseTracking};
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands
+//!         .spawn((
+//!             Camera2dBundle::default(),
+//!             EdgeScroll {
+//!                 // Don't scroll while the mouse is over the minimap in the bottom right corner.
+//!                 dead_zones: vec![Rect::new(1080.0, 520.0, 1280.0, 720.0)],
+//!                 ..default()
+//!             },
+//!         ))
+//!         .add(InitMouseTracking);
+//! }
+//! # bevy::ecs::system::assert_is_system(setup);
+//! ```
+
+use bevy::{prelude::*, render::camera::NormalizedRenderTarget, window::PrimaryWindow};
+
+use crate::{
+    mouse_pos::{pixel_size_ortho, MouseTrackingSet, TrackingSchedule},
+    MousePos,
+};
+
+/// Plugin that pans cameras with an [`EdgeScroll`] component.
+///
+/// This must be added after [`MousePosPlugin`](crate::mouse_pos::MousePosPlugin).
+pub struct EdgeScrollPlugin;
+
+impl Plugin for EdgeScrollPlugin {
+    fn build(&self, app: &mut App) {
+        let TrackingSchedule { schedule, .. } = *app
+            .world
+            .get_resource::<TrackingSchedule>()
+            .expect("`EdgeScrollPlugin` must be added after `MousePosPlugin`");
+        app.add_systems(schedule, edge_scroll.after(MouseTrackingSet::Screen));
+    }
+}
+
+/// How the speed of scrolling increases as the mouse gets closer to the edge.
+///
+/// Each curve maps the depth of the mouse into the margin, from `0.0` at the inner edge of the margin
+/// to `1.0` at the edge of the viewport, onto a fraction of the maximum speed.
+#[derive(Debug, Default, Clone, Copy)]
+pub enum EdgeScrollCurve {
+    /// Scroll at full speed anywhere within the margin.
+    Constant,
+    /// Speed up linearly towards the edge.
+    #[default]
+    Linear,
+    /// Speed up slowly at first, and then quickly near the edge.
+    Quadratic,
+    /// A custom curve.
+    Custom(fn(f32) -> f32),
+}
+
+impl EdgeScrollCurve {
+    /// Computes the fraction of the maximum speed for a depth into the margin.
+    pub fn sample(self, depth: f32) -> f32 {
+        match self {
+            Self::Constant => 1.0,
+            Self::Linear => depth,
+            Self::Quadratic => depth * depth,
+            Self::Custom(f) => f(depth),
+        }
+    }
+}
+
+/// Component that makes a camera pan when the mouse is near the edge of its viewport.
+///
+/// The camera must be tracking the mouse, such as with [`InitMouseTracking`](crate::mouse_pos::InitMouseTracking).
+#[derive(Debug, Clone, Component)]
+pub struct EdgeScroll {
+    /// How close to the edge of the viewport the mouse must be to start scrolling, in logical pixels.
+    pub margin: f32,
+    /// The fastest that the camera can scroll.
+    ///
+    /// For cameras with an [`OrthographicProjection`], this is measured in logical pixels per second,
+    /// so that scrolling feels the same at any zoom level. Otherwise, it is in world units per second.
+    pub speed: f32,
+    /// How the speed increases as the mouse gets closer to the edge.
+    pub curve: EdgeScrollCurve,
+    /// Areas of the viewport where the mouse will not cause scrolling, such as over a minimap.
+    ///
+    /// These are measured in logical pixels from the top left corner of the viewport.
+    pub dead_zones: Vec<Rect>,
+}
+
+impl Default for EdgeScroll {
+    fn default() -> Self {
+        Self {
+            margin: 20.0,
+            speed: 1000.0,
+            curve: EdgeScrollCurve::default(),
+            dead_zones: Vec::new(),
+        }
+    }
+}
+
+impl EdgeScroll {
+    /// The scrolling velocity for a position within a viewport, as a fraction of the maximum speed.
+    ///
+    /// The result is in screen-space, with +Y pointing down, and its length is at most `1.0`.
+    pub fn velocity(&self, position: Vec2, viewport: Rect) -> Vec2 {
+        let to_min = position - viewport.min;
+        let to_max = viewport.max - position;
+        if self.dead_zones.iter().any(|zone| zone.contains(to_min)) {
+            return Vec2::ZERO;
+        }
+        let depth = |distance: f32| {
+            if distance < self.margin {
+                let depth = 1.0 - distance.max(0.0) / self.margin;
+                self.curve.sample(depth)
+            } else {
+                0.0
+            }
+        };
+        // Don't scroll any faster in the corners than along the edges.
+        Vec2::new(
+            depth(to_max.x) - depth(to_min.x),
+            depth(to_max.y) - depth(to_min.y),
+        )
+        .clamp_length_max(1.0)
+    }
+}
+
+fn edge_scroll(
+    time: Res<Time>,
+    mut cameras: Query<(
+        &EdgeScroll,
+        &MousePos,
+        &Camera,
+        Option<&OrthographicProjection>,
+        &mut Transform,
+    )>,
+    windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    let primary_window = primary_window.get_single().ok();
+
+    for (scroll, &mouse_pos, camera, proj, mut transform) in &mut cameras {
+        let window = match camera.target.normalize(primary_window) {
+            Some(NormalizedRenderTarget::Window(window)) => windows.get(window.entity()).ok(),
+            _ => None,
+        };
+        let Some(window) = window else {
+            continue;
+        };
+        if !window.focused || window.cursor_position().is_none() {
+            continue;
+        }
+        let Some(viewport) = camera.logical_viewport_rect() else {
+            continue;
+        };
+        // Don't scroll while the mouse is in the viewport of a different camera.
+        if !viewport.contains(*mouse_pos) {
+            continue;
+        }
+
+        let velocity = scroll.velocity(*mouse_pos, viewport);
+        if velocity == Vec2::ZERO {
+            continue;
+        }
+        let scale = proj.map_or(Vec2::ONE, |proj| pixel_size_ortho(camera, proj));
+        // Screen-space has +Y pointing down, while the camera's local space has +Y pointing up.
+        let local = Vec2::new(velocity.x, -velocity.y) * scale * scroll.speed;
+        let delta = transform.rotation * (local.extend(0.0) * transform.scale);
+        transform.translation += delta * time.delta_seconds();
+    }
+}
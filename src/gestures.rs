@@ -0,0 +1,327 @@
+//! Recognition of mouse gestures, such as circles and swipes.
+//!
+//! After adding [`GesturePlugin`], holding the gesture button over a camera that tracks the mouse
+//! records the path of [`MousePos`] in a [`GesturePath`] component on the camera.
+//! When the button is released, the path is compared to every template in the [`GestureTemplates`] resource,
+//! and a [`GestureRecognized`] event is sent for the closest match.
+//!
+//! Matching uses the $1 unistroke recognizer, without its rotation invariance so that
+//! directional gestures such as swipes can be told apart. This means that the direction
+//! and starting point of a gesture matter: a circle drawn clockwise from the top is a different gesture
+//! than one drawn counter-clockwise. To accept both, register a template for each under the same name.
+//!
+//! The built-in templates are `"circle"` (in both directions), `"swipe_left"`, `"swipe_right"`,
+//! `"swipe_up"`, `"swipe_down"` and `"zigzag"`.
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_mouse_tracking_plugin::gestures::{GestureRecognized, GestureTemplates};
+//!
+//! // Register a "V" shape. Templates are in window coordinates, so +Y points down.
+//! fn add_templates(mut templates: ResMut<GestureTemplates>) {
+//!     templates.add("v", &[Vec2::new(0.0, 0.0), Vec2::new(1.0, 2.0), Vec2::new(2.0, 0.0)]);
+//! }
+//!
+//! fn cast_spells(mut gestures: EventReader<GestureRecognized>) {
+//!     for gesture in gestures.read() {
+//!         match gesture.name.as_str() {
+//!             "circle" => println!("Casting a shield"),
+//!             "v" => println!("Casting a fireball"),
+//!             _ => {}
+//!         }
+//!     }
+//! }
+//! # bevy::ecs::system::assert_is_system(add_templates);
+//! # bevy::ecs::system::assert_is_system(cast_spells);
+//! ```
+
+use std::f32::consts::{PI, TAU};
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    mouse_pos::{MouseTrackingSet, TrackingSchedule},
+    picking::camera_under_cursor,
+    MousePos,
+};
+
+/// The number of points that paths get resampled to before being compared.
+const RESAMPLE_POINTS: usize = 64;
+/// How far a gesture can be tilted from its template and still match, in radians.
+const ANGLE_RANGE: f32 = PI / 12.0;
+/// The precision of the search for the best angle, in radians.
+const ANGLE_PRECISION: f32 = PI / 90.0;
+
+/// Plugin that recognizes mouse gestures.
+///
+/// This must be added after [`MousePosPlugin`](crate::mouse_pos::MousePosPlugin).
+pub struct GesturePlugin {
+    /// The mouse button to hold while performing a gesture.
+    pub button: MouseButton,
+    /// The lowest score, from `0.0` to `1.0`, that counts as a match.
+    pub min_score: f32,
+    /// Paths shorter than this, in logical pixels, are ignored.
+    pub min_length: f32,
+    /// If this is `true`, the built-in templates are registered.
+    pub builtin_templates: bool,
+}
+
+impl Default for GesturePlugin {
+    fn default() -> Self {
+        Self {
+            button: MouseButton::Right,
+            min_score: 0.8,
+            min_length: 20.0,
+            builtin_templates: true,
+        }
+    }
+}
+
+/// The settings for [`GesturePlugin`].
+#[derive(Resource)]
+struct GestureSettings {
+    button: MouseButton,
+    min_score: f32,
+    min_length: f32,
+}
+
+impl Plugin for GesturePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GestureSettings {
+            button: self.button,
+            min_score: self.min_score,
+            min_length: self.min_length,
+        });
+        let mut templates = app
+            .world
+            .get_resource_or_insert_with(GestureTemplates::default);
+        if self.builtin_templates {
+            templates.add_builtins();
+        }
+        let TrackingSchedule { schedule, .. } = *app
+            .world
+            .get_resource::<TrackingSchedule>()
+            .expect("`GesturePlugin` must be added after `MousePosPlugin`");
+        app.add_event::<GestureRecognized>();
+        app.add_systems(
+            schedule,
+            (start_gesture, update_gesture)
+                .chain()
+                .after(MouseTrackingSet::Screen),
+        );
+    }
+}
+
+/// Resource containing the gestures that can be recognized.
+#[derive(Debug, Default, Clone, Resource)]
+pub struct GestureTemplates {
+    templates: Vec<(String, Vec<Vec2>)>,
+}
+
+impl GestureTemplates {
+    /// Registers a template with the given name, which may be shared by several templates.
+    ///
+    /// The points are in window coordinates, with +Y pointing down. Only the shape of the
+    /// path matters, since it gets normalized before being compared.
+    pub fn add(&mut self, name: impl Into<String>, points: &[Vec2]) {
+        if let Some(points) = normalize(points) {
+            self.templates.push((name.into(), points));
+        }
+    }
+
+    fn add_builtins(&mut self) {
+        let circle = |dir: f32| {
+            (0..=32)
+                .map(|i| {
+                    let t = i as f32 / 32.0 * TAU;
+                    Vec2::new(dir * t.sin(), -t.cos())
+                })
+                .collect::<Vec<_>>()
+        };
+        self.add("circle", &circle(1.0));
+        self.add("circle", &circle(-1.0));
+        self.add("swipe_left", &[Vec2::ZERO, Vec2::NEG_X]);
+        self.add("swipe_right", &[Vec2::ZERO, Vec2::X]);
+        self.add("swipe_up", &[Vec2::ZERO, Vec2::NEG_Y]);
+        self.add("swipe_down", &[Vec2::ZERO, Vec2::Y]);
+        self.add(
+            "zigzag",
+            &[
+                Vec2::new(0.0, 0.0),
+                Vec2::new(1.0, 1.0),
+                Vec2::new(2.0, 0.0),
+                Vec2::new(3.0, 1.0),
+                Vec2::new(4.0, 0.0),
+            ],
+        );
+    }
+
+    /// Finds the template that best matches a path, returning its name and score.
+    ///
+    /// The score ranges from `0.0` to `1.0`, where `1.0` is a perfect match.
+    pub fn recognize(&self, points: &[Vec2]) -> Option<(&str, f32)> {
+        let points = normalize(points)?;
+        self.templates
+            .iter()
+            .map(|(name, template)| (name.as_str(), best_distance(&points, template)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            // Paths are scaled to fit in a unit square, so half of its diagonal is the worst case.
+            .map(|(name, distance)| (name, (1.0 - distance / 0.5_f32.hypot(0.5)).max(0.0)))
+    }
+}
+
+/// Component that exists on a camera while a gesture is being performed with it.
+#[derive(Debug, Default, Clone, PartialEq, Component)]
+pub struct GesturePath {
+    /// The screen-space path of the mouse so far.
+    pub points: Vec<Vec2>,
+}
+
+/// Event sent when a gesture is recognized.
+#[derive(Debug, Clone, PartialEq, Event)]
+pub struct GestureRecognized {
+    /// The name of the matching template.
+    pub name: String,
+    /// How closely the gesture matched the template, from `0.0` to `1.0`.
+    pub score: f32,
+    /// The camera that the gesture was performed in.
+    pub camera: Entity,
+}
+
+fn path_length(points: &[Vec2]) -> f32 {
+    points.windows(2).map(|w| w[0].distance(w[1])).sum()
+}
+
+/// Resamples a path into evenly spaced points, then scales it to fit in a unit square
+/// and moves its centroid to the origin. Returns `None` if the path has no length.
+fn normalize(points: &[Vec2]) -> Option<Vec<Vec2>> {
+    let length = path_length(points);
+    if length <= 0.0 {
+        return None;
+    }
+
+    let interval = length / (RESAMPLE_POINTS - 1) as f32;
+    let mut resampled = vec![points[0]];
+    let mut traveled = 0.0;
+    let mut prev = points[0];
+    let mut rest = points[1..].iter().copied();
+    let mut next = rest.next();
+    while let Some(point) = next {
+        let d = prev.distance(point);
+        if traveled + d >= interval && d > 0.0 {
+            let q = prev.lerp(point, (interval - traveled) / d);
+            resampled.push(q);
+            // Continue from the new point, towards the same point as before.
+            prev = q;
+            traveled = 0.0;
+        } else {
+            traveled += d;
+            prev = point;
+            next = rest.next();
+        }
+    }
+    // Rounding errors can leave us a point short.
+    resampled.resize(RESAMPLE_POINTS, *points.last().unwrap());
+    resampled.truncate(RESAMPLE_POINTS);
+
+    // Scale uniformly, so that one-dimensional gestures such as swipes don't get stretched.
+    let min = resampled.iter().fold(Vec2::INFINITY, |min, &p| min.min(p));
+    let max = resampled
+        .iter()
+        .fold(Vec2::NEG_INFINITY, |max, &p| max.max(p));
+    let scale = (max - min).max_element();
+    let centroid = resampled.iter().sum::<Vec2>() / resampled.len() as f32;
+    Some(
+        resampled
+            .into_iter()
+            .map(|p| (p - centroid) / scale)
+            .collect(),
+    )
+}
+
+/// The average distance between the points of two paths, after rotating the first one by `angle`.
+fn path_distance(points: &[Vec2], template: &[Vec2], angle: f32) -> f32 {
+    let rotation = Vec2::from_angle(angle);
+    let total: f32 = points
+        .iter()
+        .zip(template)
+        .map(|(&p, &t)| rotation.rotate(p).distance(t))
+        .sum();
+    total / points.len() as f32
+}
+
+/// Finds the smallest distance between two paths, within a small range of rotations,
+/// using a golden section search.
+fn best_distance(points: &[Vec2], template: &[Vec2]) -> f32 {
+    let phi = 0.5 * (5.0_f32.sqrt() - 1.0);
+    let (mut a, mut b) = (-ANGLE_RANGE, ANGLE_RANGE);
+    let mut x1 = phi * a + (1.0 - phi) * b;
+    let mut x2 = (1.0 - phi) * a + phi * b;
+    let mut f1 = path_distance(points, template, x1);
+    let mut f2 = path_distance(points, template, x2);
+    while (b - a).abs() > ANGLE_PRECISION {
+        if f1 < f2 {
+            (b, x2, f2) = (x2, x1, f1);
+            x1 = phi * a + (1.0 - phi) * b;
+            f1 = path_distance(points, template, x1);
+        } else {
+            (a, x1, f1) = (x1, x2, f2);
+            x2 = (1.0 - phi) * a + phi * b;
+            f2 = path_distance(points, template, x2);
+        }
+    }
+    f1.min(f2)
+}
+
+fn start_gesture(
+    mut commands: Commands,
+    settings: Res<GestureSettings>,
+    buttons: Res<Input<MouseButton>>,
+    cameras: Query<(Entity, &Camera, &MousePos)>,
+    windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    if !buttons.just_pressed(settings.button) {
+        return;
+    }
+    if let Some(camera) =
+        camera_under_cursor(cameras.iter(), &windows, primary_window.get_single().ok())
+    {
+        let (.., &mouse_pos) = cameras.get(camera).unwrap();
+        commands.entity(camera).insert(GesturePath {
+            points: vec![*mouse_pos],
+        });
+    }
+}
+
+fn update_gesture(
+    mut commands: Commands,
+    settings: Res<GestureSettings>,
+    buttons: Res<Input<MouseButton>>,
+    templates: Res<GestureTemplates>,
+    mut cameras: Query<(Entity, &mut GesturePath, &MousePos)>,
+    mut events: EventWriter<GestureRecognized>,
+) {
+    for (camera, mut path, &mouse_pos) in &mut cameras {
+        if path.points.last() != Some(&*mouse_pos) {
+            path.points.push(*mouse_pos);
+        }
+        if buttons.pressed(settings.button) {
+            continue;
+        }
+
+        commands.entity(camera).remove::<GesturePath>();
+        if path_length(&path.points) < settings.min_length {
+            continue;
+        }
+        if let Some((name, score)) = templates.recognize(&path.points) {
+            if score >= settings.min_score {
+                events.send(GestureRecognized {
+                    name: name.to_string(),
+                    score,
+                    camera,
+                });
+            }
+        }
+    }
+}
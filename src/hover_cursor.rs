@@ -0,0 +1,106 @@
+//! Contextual cursor icons, which change based on what the mouse is hovering over.
+//!
+//! After adding [`HoverCursorPlugin`], give an entity a [`HoverCursor`] component and the icon
+//! of the OS cursor will change while the mouse is over that entity.
+//! Entities are hit-tested using the [`picking`](crate::picking) module.
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_mouse_tracking_plugin::hover_cursor::HoverCursor;
+//!
+//! fn spawn_unit(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.spawn((
+//!         SpriteBundle {
+//!             texture: asset_server.load("unit.png"),
+//!             ..default()
+//!         },
+//!         HoverCursor(CursorIcon::Hand),
+//!     ));
+//! }
+//! # bevy::ecs::system::assert_is_system(spawn_unit);
+//! ```
+
+use bevy::{
+    prelude::*, render::camera::NormalizedRenderTarget, utils::HashMap, window::PrimaryWindow,
+};
+
+use crate::{
+    picking::{camera_under_cursor, HoveredEntities, PickingPlugin, PickingSet},
+    MousePos,
+};
+
+/// Plugin that sets the cursor icon of each window, based on the [`HoverCursor`] under the mouse.
+///
+/// This adds [`PickingPlugin`] if it hasn't been added already.
+pub struct HoverCursorPlugin;
+
+impl Plugin for HoverCursorPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<PickingPlugin>() {
+            app.add_plugins(PickingPlugin);
+        }
+        app.init_resource::<DefaultCursorIcon>();
+        app.add_systems(PostUpdate, update_cursor_icon.after(PickingSet));
+    }
+}
+
+/// Component that sets the icon of the cursor while the mouse is hovering over this entity.
+///
+/// Only the frontmost entity under the mouse is considered, so this has no effect while
+/// another entity without a `HoverCursor` is in front of it. If several cameras overlap under the mouse,
+/// only the one that renders last is considered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Component)]
+pub struct HoverCursor(pub CursorIcon);
+
+/// Resource containing the icon to use when the mouse is not over any [`HoverCursor`].
+///
+/// This can be changed at runtime, such as to show a different cursor while a unit is selected.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub struct DefaultCursorIcon(pub CursorIcon);
+
+fn update_cursor_icon(
+    default_icon: Res<DefaultCursorIcon>,
+    cameras: Query<(Entity, &Camera, &MousePos, &HoveredEntities)>,
+    targets: Query<&HoverCursor>,
+    mut windows: Query<&mut Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    let primary_window = primary_window.get_single().ok();
+
+    // Depths from different cameras can't be compared, so only the camera drawn on top counts.
+    let active = camera_under_cursor(
+        cameras
+            .iter()
+            .map(|(entity, camera, pos, _)| (entity, camera, pos)),
+        &windows.to_readonly(),
+        primary_window,
+    );
+
+    // For each window, the icon of the frontmost hovered entity, if it has one.
+    let mut icons = HashMap::<Entity, Option<CursorIcon>>::new();
+    for (entity, camera, _, hovered) in &cameras {
+        let Some(NormalizedRenderTarget::Window(window)) = camera.target.normalize(primary_window)
+        else {
+            continue;
+        };
+        let icon = icons.entry(window.entity()).or_default();
+        if active == Some(entity) {
+            // Only the frontmost entity counts, so an entity can't set the icon through another one.
+            *icon = hovered
+                .top()
+                .and_then(|entity| targets.get(entity).ok())
+                .map(|&HoverCursor(icon)| icon);
+        }
+    }
+
+    for (window, icon) in icons {
+        let Ok(mut window) = windows.get_mut(window) else {
+            continue;
+        };
+        let icon = icon.unwrap_or(default_icon.0);
+        // Avoid triggering change detection when the icon stays the same.
+        if window.cursor.icon != icon {
+            window.cursor.icon = icon;
+        }
+    }
+}
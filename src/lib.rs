@@ -29,7 +29,7 @@
 //! // First, add the plugin to your `App`.
 //!
 //! App::new()
-//!     .add_plugins((DefaultPlugins, MousePosPlugin))
+//!     .add_plugins((DefaultPlugins, MousePosPlugin::default()))
 //!     .add_systems(Startup, setup)
 //!     .add_systems(Update, dbg_mouse)
 //!     // ...
@@ -64,7 +64,7 @@
 //! # use bevy::prelude::*;
 //! # use bevy_mouse_tracking_plugin::{prelude::*, MousePos};
 //! # App::new()
-//! #    .add_plugins((DefaultPlugins, MousePosPlugin))
+//! #    .add_plugins((DefaultPlugins, MousePosPlugin::default()))
 //! #    .add_systems(Startup, setup)
 //! #    .add_systems(Update, dbg_mouse)
 //! #    .update();
@@ -97,7 +97,7 @@
 //! # use bevy::prelude::*;
 //! # use bevy_mouse_tracking_plugin::{prelude::*, MousePos, MainCamera};
 //! # App::new()
-//! #    .add_plugins((DefaultPlugins, MousePosPlugin))
+//! #    .add_plugins((DefaultPlugins, MousePosPlugin::default()))
 //! #    .add_systems(Startup, setup)
 //! #    .add_systems(Update, (dbg_world_single, dbg_world_res))
 //! #    .update();
@@ -130,23 +130,129 @@
 //!
 //! If you do not specify a [`MainCamera`], the [`MousePos`] and [`MousePosWorld`]
 //! resources will still exist, but they will always be zero.
+//! Alternatively, you can set [`MousePosPlugin::auto_main_camera`] to have the plugin pick one for you.
 //!
 //! # Mouse motion
 //!
 //! This crate supports a resource that tracks mouse motion, via [`MouseMotionPlugin`].
 //! The motion can be accessed from any system in a [`MouseMotion`] resource.
 //!
+//! In apps with several windows or viewports, each [`Window`] also gets a [`MouseMotion`] component
+//! that only receives the motion intended for that window. You can add the same component to a camera.
+//!
+//! # Picking
+//!
+//! [`PickingPlugin`] finds the entities under the mouse, for every camera that tracks the world-space mouse position.
+//! They are listed in a [`HoveredEntities`] component on the camera, and [`PointerOver`] and [`PointerOut`] events
+//! are sent as the mouse moves over and off of them. Entities are hit-tested against their [`PickShape`],
+//! their sprite, or their `Aabb`.
+//!
+//! To send mouse events to individual entities, add [`PointerEventsPlugin`] and give the entities a [`Pickable`] component.
+//! They will receive events such as [`CursorEnter`] and [`CursorClick`], which bubble up the entity hierarchy.
+//!
+//! # Selection
+//!
+//! [`SelectionPlugin`] lets the user select [`Selectable`] entities by dragging a box or a lasso around them,
+//! depending on the [`SelectionTool`] resource.
+//! Selected entities get a [`Selected`] component.
+//!
+//! # Gestures
+//!
+//! [`GesturePlugin`] recognizes mouse gestures, such as circles and swipes, and sends a [`GestureRecognized`] event
+//! for each one. Custom gestures can be added to the [`GestureTemplates`] resource.
+//!
+//! # Edge scrolling
+//!
+//! With [`EdgeScrollPlugin`], cameras with an [`EdgeScroll`] component pan when the mouse nears the edge of their viewport.
+//!
+//! # Hover cursors
+//!
+//! To change the cursor icon while the mouse is over an entity, add [`HoverCursorPlugin`]
+//! and give the entity a [`HoverCursor`] component.
+//!
+//! # Cursor sprite
+//!
+//! With the `sprite` feature enabled, `CursorSpritePlugin` can draw the cursor as a sprite instead of using the OS cursor.
+//! Add a `CursorSprite` component to a camera that tracks the mouse, and the plugin will spawn the sprite
+//! and keep it under the mouse, with no frame lag.
+//!
 //! [`Res`]: bevy::ecs::system::Res
+//! [`Window`]: bevy::window::Window
+//! [`HoverCursorPlugin`]: hover_cursor::HoverCursorPlugin
+//! [`PickingPlugin`]: picking::PickingPlugin
+//! [`PointerOver`]: picking::PointerOver
+//! [`PointerOut`]: picking::PointerOut
+//! [`PointerEventsPlugin`]: pointer_events::PointerEventsPlugin
+//! [`CursorEnter`]: pointer_events::CursorEnter
+//! [`CursorClick`]: pointer_events::CursorClick
+//! [`SelectionPlugin`]: selection::SelectionPlugin
+//! [`GesturePlugin`]: gestures::GesturePlugin
+//! [`GestureTemplates`]: gestures::GestureTemplates
+//! [`EdgeScrollPlugin`]: edge_scroll::EdgeScrollPlugin
+//! [`MainCamera`]: struct@MainCamera
+//! [`MousePosPlugin::auto_main_camera`]: mouse_pos::MousePosPlugin::auto_main_camera
 
 #![allow(clippy::type_complexity)]
 
 pub mod prelude {
+    pub use crate::conditions::{
+        cursor_moved, cursor_moved_in, cursor_world_moved, mouse_motion_nonzero,
+    };
+    pub use crate::edge_scroll::EdgeScrollPlugin;
+    pub use crate::gestures::GesturePlugin;
+    pub use crate::hover_cursor::HoverCursorPlugin;
     pub use crate::mouse_motion::MouseMotionPlugin;
-    pub use crate::mouse_pos::{InitMouseTracking, InitWorldTracking, MousePosPlugin};
+    pub use crate::mouse_pos::{
+        InitMouseTracking, InitNdcTracking, InitPhysicalTracking, InitWorldTracking,
+        MousePosPlugin, MouseTrackingSet,
+    };
+    pub use crate::picking::{PickingPlugin, PickingSet, PointerOut, PointerOver};
+    pub use crate::pointer_events::{
+        CursorClick, CursorDown, CursorEnter, CursorExit, CursorUp, PointerEventsPlugin,
+    };
+    pub use crate::selection::SelectionPlugin;
+    pub use crate::snapping::InitTileTracking;
+
+    #[cfg(feature = "sprite")]
+    pub use crate::cursor_sprite::CursorSpritePlugin;
 }
 
 pub mod mouse_pos;
-pub use mouse_pos::{MainCamera, MousePos, MousePosWorld};
+pub use mouse_pos::{MousePos, MousePosNdc, MousePosPhysical, MousePosWorld};
+
+pub mod main_camera;
+pub use main_camera::MainCamera;
+
+pub mod snapping;
+pub use snapping::{MouseTile, PixelSnapping};
+
+pub mod window_cursor;
+pub use window_cursor::{DesktopCursorPos, FocusedWindowCursor, WindowCursor};
+
+pub mod conditions;
 
 pub mod mouse_motion;
-pub use mouse_motion::MouseMotion;
+pub use mouse_motion::{CursorLock, MouseMotion};
+
+pub mod picking;
+pub use picking::{HoveredEntities, PickShape};
+
+pub mod pointer_events;
+pub use pointer_events::Pickable;
+
+pub mod hover_cursor;
+pub use hover_cursor::HoverCursor;
+
+pub mod selection;
+pub use selection::{Selectable, Selected, SelectionTool};
+
+pub mod gestures;
+pub use gestures::GestureRecognized;
+
+pub mod edge_scroll;
+pub use edge_scroll::EdgeScroll;
+
+#[cfg(feature = "sprite")]
+pub mod cursor_sprite;
+#[cfg(feature = "sprite")]
+pub use cursor_sprite::CursorSprite;
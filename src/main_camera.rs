@@ -0,0 +1,329 @@
+//! Global access to the tracking components of the main camera.
+//!
+//! Marking a camera with [`MainCamera`](struct@MainCamera) causes the plugin to mirror its tracking components
+//! (such as [`MousePos`] and [`MousePosWorld`]) into global resources of the same type.
+//!
+//! If you need convenient global access for more than one camera, you can tag each of them:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_mouse_tracking_plugin::{
+//!     main_camera::{MainCameraPlugin, MousePosWorldOf},
+//!     prelude::*,
+//!     MainCamera,
+//! };
+//!
+//! // Tags can be any type.
+//! struct Editor;
+//!
+//! App::new()
+//!     .add_plugins((DefaultPlugins, MousePosPlugin::default()))
+//!     // Each tag needs its own plugin.
+//!     .add_plugins(MainCameraPlugin::<Editor>::default())
+//!     .add_systems(Startup, setup)
+//!     .add_systems(Update, dbg_editor_mouse)
+//!     // ...
+//! #    .update();
+//!
+//! fn setup(mut commands: Commands) {
+//!     // The untagged main camera works as usual.
+//!     commands
+//!         .spawn(Camera2dBundle::default())
+//!         .add(InitWorldTracking)
+//!         .insert(MainCamera);
+//!     // The tagged camera gets its own set of resources.
+//!     commands
+//!         .spawn(Camera2dBundle::default())
+//!         .add(InitWorldTracking)
+//!         .insert(MainCamera::<Editor>::default());
+//! }
+//!
+//! fn dbg_editor_mouse(mouse: Res<MousePosWorldOf<Editor>>) {
+//!     eprintln!("{}", **mouse);
+//! }
+//! ```
+
+use std::{marker::PhantomData, ops::Deref};
+
+use bevy::{
+    ecs::schedule::SystemConfigs,
+    prelude::*,
+    render::camera::RenderTarget,
+    window::{PrimaryWindow, WindowRef},
+};
+
+use crate::{
+    mouse_pos::{MouseTrackingSet, TrackingSchedule},
+    MousePos, MousePosNdc, MousePosPhysical, MousePosWorld, MouseTile,
+};
+
+/// Marker component for the main camera. If no main camera is specified, all cameras will be treated equally.
+///
+/// The type parameter `Tag` can be used to mark several main cameras, each of which will get
+/// its own [`TrackingOf`] resources. See [`MainCameraPlugin`] for more details.
+#[derive(Component)]
+pub struct MainCamera<Tag = ()> {
+    _tag: PhantomData<fn() -> Tag>,
+}
+
+/// The marker for the untagged main camera.
+#[allow(non_upper_case_globals)]
+pub const MainCamera: MainCamera = MainCamera { _tag: PhantomData };
+
+impl<Tag> Default for MainCamera<Tag> {
+    fn default() -> Self {
+        Self { _tag: PhantomData }
+    }
+}
+
+/// A global resource that tracks the component `T` of the camera marked with [`MainCamera<Tag>`](struct@MainCamera).
+///
+/// For the untagged main camera, the component gets tracked in a resource of type `T` instead.
+#[derive(Resource)]
+pub struct TrackingOf<Tag, T> {
+    value: T,
+    _tag: PhantomData<fn() -> Tag>,
+}
+
+impl<Tag, T: Default> Default for TrackingOf<Tag, T> {
+    fn default() -> Self {
+        Self {
+            value: default(),
+            _tag: PhantomData,
+        }
+    }
+}
+
+impl<Tag, T> Deref for TrackingOf<Tag, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+/// The [`MousePos`] of the camera marked with [`MainCamera<Tag>`](struct@MainCamera).
+pub type MousePosOf<Tag> = TrackingOf<Tag, MousePos>;
+
+/// The [`MousePosWorld`] of the camera marked with [`MainCamera<Tag>`](struct@MainCamera).
+pub type MousePosWorldOf<Tag> = TrackingOf<Tag, MousePosWorld>;
+
+/// Plugin that maintains the [`TrackingOf`] resources for the camera marked with [`MainCamera<Tag>`](struct@MainCamera).
+///
+/// This must be added after [`MousePosPlugin`](crate::mouse_pos::MousePosPlugin), which already handles the untagged main camera.
+pub struct MainCameraPlugin<Tag> {
+    _tag: PhantomData<fn() -> Tag>,
+}
+
+impl<Tag> Default for MainCameraPlugin<Tag> {
+    fn default() -> Self {
+        Self { _tag: PhantomData }
+    }
+}
+
+impl<Tag: 'static> Plugin for MainCameraPlugin<Tag> {
+    fn build(&self, app: &mut App) {
+        let TrackingSchedule {
+            schedule,
+            recompute_after_propagation,
+        } = *app
+            .world
+            .get_resource::<TrackingSchedule>()
+            .expect("`MainCameraPlugin` must be added after `MousePosPlugin`");
+        app.init_resource::<CurrentMainCamera<Tag>>()
+            .add_event::<MainCameraChanged<Tag>>()
+            .init_resource::<MousePosOf<Tag>>()
+            .init_resource::<MousePosWorldOf<Tag>>()
+            .init_resource::<TrackingOf<Tag, MousePosPhysical>>()
+            .init_resource::<TrackingOf<Tag, MousePosNdc>>()
+            .init_resource::<TrackingOf<Tag, MouseTile>>()
+            .add_systems(schedule, tagged_systems::<Tag>());
+        if recompute_after_propagation {
+            app.add_systems(PostUpdate, tagged_systems::<Tag>());
+        }
+    }
+}
+
+fn tagged_systems<Tag: 'static>() -> SystemConfigs {
+    (
+        update_main_camera::<Tag>,
+        (
+            update_tagged_resource::<Tag, MousePos>,
+            update_tagged_resource::<Tag, MousePosWorld>,
+            update_tagged_resource::<Tag, MousePosPhysical>,
+            update_tagged_resource::<Tag, MousePosNdc>,
+            update_tagged_resource::<Tag, MouseTile>,
+        ),
+    )
+        .chain()
+        .in_set(MouseTrackingSet::Resources)
+}
+
+/// What to do when more than one entity is marked with the same [`MainCamera`](struct@MainCamera) component.
+///
+/// This is configured via [`MousePosPlugin::conflict_policy`](crate::mouse_pos::MousePosPlugin::conflict_policy),
+/// and applies to every tag. Whenever the resolved main camera switches, a [`MainCameraChanged`] event is sent.
+#[derive(Debug, Default, Resource, Clone, Copy, PartialEq, Eq)]
+pub enum MainCameraConflict {
+    /// Panic when there is more than one main camera.
+    #[default]
+    Panic,
+    /// Log a warning, and use the camera with the highest [`Camera::order`].
+    HighestOrder,
+    /// Log a warning, and use the camera that was most recently marked as the main camera.
+    MostRecent,
+    /// Log a warning, and use the camera that is active.
+    /// If more than one of them is active, the one with the highest [`Camera::order`] will be used.
+    Active,
+}
+
+/// Event that gets sent whenever the camera marked with [`MainCamera<Tag>`](struct@MainCamera) switches to a different entity.
+#[derive(Debug, Event, Clone, Copy, PartialEq, Eq)]
+pub struct MainCameraChanged<Tag = ()> {
+    /// The previous main camera, if there was one.
+    pub previous: Option<Entity>,
+    /// The new main camera, if there is one.
+    pub current: Option<Entity>,
+    _tag: PhantomData<fn() -> Tag>,
+}
+
+/// The entity that is currently being used as the [`MainCamera<Tag>`](struct@MainCamera), if any.
+#[derive(Resource)]
+pub(crate) struct CurrentMainCamera<Tag = ()> {
+    entity: Option<Entity>,
+    /// All entities with the marker component, in the order that the marker was added.
+    marked: Vec<Entity>,
+    /// Whether to pick a main camera automatically if there are no entities with the marker.
+    auto_select: bool,
+    _tag: PhantomData<fn() -> Tag>,
+}
+
+impl<Tag> CurrentMainCamera<Tag> {
+    pub(crate) fn new(auto_select: bool) -> Self {
+        Self {
+            entity: None,
+            marked: Vec::new(),
+            auto_select,
+            _tag: PhantomData,
+        }
+    }
+}
+
+impl<Tag> Default for CurrentMainCamera<Tag> {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+pub(crate) fn update_main_camera<Tag: 'static>(
+    mut current: ResMut<CurrentMainCamera<Tag>>,
+    policy: Res<MainCameraConflict>,
+    added_main: Query<Entity, Added<MainCamera<Tag>>>,
+    with_marker: Query<(), With<MainCamera<Tag>>>,
+    cameras: Query<(Entity, &Camera, Has<MousePos>)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut changed: EventWriter<MainCameraChanged<Tag>>,
+) {
+    let current = &mut *current;
+    let was_conflicted = current.marked.len() > 1;
+    // Ditch any removed components, and keep track of the new ones.
+    current.marked.retain(|&e| with_marker.contains(e));
+    for added in &added_main {
+        if !current.marked.contains(&added) {
+            current.marked.push(added);
+        }
+    }
+
+    let main = match *current.marked {
+        [main] => Some(main),
+        [] if current.auto_select => auto_select(&cameras, primary_window.get_single().ok()),
+        [] => None,
+        [..] => {
+            let name = std::any::type_name::<MainCamera<Tag>>();
+            if *policy == MainCameraConflict::Panic {
+                panic!("`bevy_mouse_tracking_plugin`: there cannot be more than one entity with a `{name}` component");
+            }
+            if !was_conflicted {
+                warn!(
+                    "`bevy_mouse_tracking_plugin`: there is more than one entity with a `{name}` component. Resolving using {:?}",
+                    *policy,
+                );
+            }
+            resolve_conflict(*policy, &current.marked, &cameras)
+        }
+    };
+
+    if current.entity != main {
+        changed.send(MainCameraChanged {
+            previous: current.entity,
+            current: main,
+            _tag: PhantomData,
+        });
+        current.entity = main;
+    }
+}
+
+/// Picks one of several main cameras, according to the conflict policy.
+fn resolve_conflict(
+    policy: MainCameraConflict,
+    marked: &[Entity],
+    cameras: &Query<(Entity, &Camera, Has<MousePos>)>,
+) -> Option<Entity> {
+    let order = |e: Entity| cameras.get(e).map_or(isize::MIN, |(_, c, _)| c.order);
+    let is_active = |e: Entity| cameras.get(e).is_ok_and(|(_, c, _)| c.is_active);
+    match policy {
+        MainCameraConflict::Panic => unreachable!(),
+        MainCameraConflict::HighestOrder => marked.iter().copied().max_by_key(|&e| order(e)),
+        MainCameraConflict::MostRecent => marked.last().copied(),
+        MainCameraConflict::Active => marked
+            .iter()
+            .copied()
+            .max_by_key(|&e| (is_active(e), order(e))),
+    }
+}
+
+/// Picks the active camera with the highest [`Camera::order`] out of all tracked cameras
+/// that render to the primary window.
+fn auto_select(
+    cameras: &Query<(Entity, &Camera, Has<MousePos>)>,
+    primary_window: Option<Entity>,
+) -> Option<Entity> {
+    let primary = RenderTarget::Window(WindowRef::Primary).normalize(primary_window)?;
+    cameras
+        .iter()
+        .filter(|&(_, camera, tracked)| {
+            tracked
+                && camera.is_active
+                && camera.target.normalize(primary_window).as_ref() == Some(&primary)
+        })
+        .max_by_key(|(_, camera, _)| camera.order)
+        .map(|(entity, ..)| entity)
+}
+
+/// Gets the value of a tracking component for the main camera, or zero if there is no main camera.
+fn main_value<T: Component + Copy + Default>(main: Option<Entity>, tracking: &Query<&T>) -> T {
+    main.and_then(|main| tracking.get(main).ok())
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Copies a tracking component from the main camera into the corresponding resource.
+/// If there is no main camera, the resource gets zeroed out.
+pub(crate) fn update_resource<T: Component + Resource + Copy + PartialEq + Default>(
+    main: Res<CurrentMainCamera>,
+    mut res: ResMut<T>,
+    tracking: Query<&T>,
+) {
+    res.set_if_neq(main_value(main.entity, &tracking));
+}
+
+fn update_tagged_resource<Tag: 'static, T: Component + Copy + PartialEq + Default>(
+    main: Res<CurrentMainCamera<Tag>>,
+    mut res: ResMut<TrackingOf<Tag, T>>,
+    tracking: Query<&T>,
+) {
+    let value = main_value(main.entity, &tracking);
+    if res.value != value {
+        res.value = value;
+    }
+}
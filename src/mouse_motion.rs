@@ -1,11 +1,62 @@
-use bevy::prelude::*;
+use bevy::{
+    ecs::schedule::ScheduleLabel,
+    input::InputSystem,
+    prelude::*,
+    render::camera::RenderTarget,
+    window::{CursorGrabMode, PrimaryWindow, WindowFocused, WindowRef},
+};
 
 use bevy::input::mouse::MouseMotion as BevyMouseMotion;
 
+use crate::mouse_pos::{MouseTrackingSet, TrackingSchedule};
+
 /// Plugin that tracks mouse motion.
-pub struct MouseMotionPlugin;
+///
+/// If [`MousePosPlugin::schedule`](crate::mouse_pos::MousePosPlugin::schedule) is changed,
+/// this plugin must be added after `MousePosPlugin`, so that [`CursorLock`] gets applied in the same schedule.
+#[derive(Default)]
+pub struct MouseMotionPlugin {
+    /// If this is `true`, mouse motion will be ignored while none of the app's windows have focus.
+    ///
+    /// Mouse motion is reported by the OS even while the app is in the background,
+    /// so otherwise alt-tabbing away from a game could cause its camera to spin.
+    pub ignore_unfocused: bool,
+    /// The number of mouse motion events to ignore after the app regains focus.
+    /// This can be used to get rid of the jump that occurs when clicking back into a window.
+    pub skip_after_focus: usize,
+}
+
+/// The focus settings for [`MouseMotionPlugin`].
+#[derive(Resource)]
+struct MotionFocusSettings {
+    ignore_unfocused: bool,
+    skip_after_focus: usize,
+}
+
+/// Keeps track of the app's focus in between frames.
+struct FocusState {
+    was_focused: bool,
+    to_skip: usize,
+}
 
-#[derive(Debug, Resource, Clone, Copy, PartialEq, Event)]
+impl Default for FocusState {
+    fn default() -> Self {
+        Self {
+            was_focused: true,
+            to_skip: 0,
+        }
+    }
+}
+
+/// The motion of the mouse during the current frame.
+///
+/// As a resource, this contains all of the mouse motion.
+/// As a component, it only contains the motion intended for that entity.
+/// Every [`Window`] gets this component automatically, and you can add it to a camera yourself.
+///
+/// Motion goes to the window under the cursor, or to the focused window if the cursor is not over any window.
+/// A camera on that window receives the motion if its viewport contains the cursor.
+#[derive(Debug, Default, Resource, Component, Clone, Copy, PartialEq, Event)]
 pub struct MouseMotion {
     pub delta: Vec2,
 }
@@ -14,14 +65,186 @@ impl bevy::app::Plugin for MouseMotionPlugin {
     fn build(&self, app: &mut bevy::app::App) {
         app.add_event::<MouseMotion>();
         app.insert_resource(MouseMotion { delta: Vec2::ZERO });
+        app.insert_resource(MotionFocusSettings {
+            ignore_unfocused: self.ignore_unfocused,
+            skip_after_focus: self.skip_after_focus,
+        });
+        app.init_resource::<CursorLock>();
         app.add_systems(
             First,
-            update_mouse_motion.after(bevy::ecs::event::event_update_system::<MouseMotion>),
+            (update_mouse_motion, attribute_mouse_motion)
+                .chain()
+                .after(bevy::ecs::event::event_update_system::<MouseMotion>),
+        );
+        // The lock has to be applied before the cursor position gets read, wherever that happens.
+        let schedule = app
+            .world
+            .get_resource::<TrackingSchedule>()
+            .map_or(PreUpdate.intern(), |tracking| tracking.schedule);
+        app.add_systems(
+            schedule,
+            update_cursor_lock
+                .after(InputSystem)
+                .before(MouseTrackingSet::Screen),
         );
     }
 }
 
-fn update_mouse_motion(mut events: EventReader<BevyMouseMotion>, mut res: ResMut<MouseMotion>) {
-    let delta = events.read().fold(Vec2::ZERO, |acc, e| acc + e.delta);
+fn update_mouse_motion(
+    mut state: Local<FocusState>,
+    mut events: EventReader<BevyMouseMotion>,
+    mut res: ResMut<MouseMotion>,
+    settings: Res<MotionFocusSettings>,
+    windows: Query<&Window>,
+) {
+    let focused = windows.iter().any(|w| w.focused);
+    if focused && !state.was_focused {
+        state.to_skip = settings.skip_after_focus;
+    }
+    state.was_focused = focused;
+
+    if settings.ignore_unfocused && !focused {
+        events.clear();
+        *res = MouseMotion { delta: Vec2::ZERO };
+        return;
+    }
+
+    let delta = events
+        .read()
+        .filter(|_| {
+            let skip = state.to_skip > 0;
+            state.to_skip = state.to_skip.saturating_sub(1);
+            !skip
+        })
+        .fold(Vec2::ZERO, |acc, e| acc + e.delta);
     *res = MouseMotion { delta };
 }
+
+fn attribute_mouse_motion(
+    mut commands: Commands,
+    motion: Res<MouseMotion>,
+    new_windows: Query<Entity, (With<Window>, Without<MouseMotion>)>,
+    mut windows: Query<(Entity, &Window, &mut MouseMotion)>,
+    mut cameras: Query<(&Camera, &mut MouseMotion), Without<Window>>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    for entity in &new_windows {
+        commands.entity(entity).insert(MouseMotion::default());
+    }
+
+    // Prefer the window that the cursor is over, and fall back to the focused window.
+    let target = windows
+        .iter()
+        .filter(|(_, window, _)| window.cursor_position().is_some() || window.focused)
+        .max_by_key(|(_, window, _)| (window.cursor_position().is_some(), window.focused))
+        .map(|(entity, window, _)| (entity, window.cursor_position()));
+
+    for (entity, _, mut window_motion) in windows.iter_mut() {
+        let value = match target {
+            Some((target, _)) if target == entity => *motion,
+            _ => MouseMotion::default(),
+        };
+        window_motion.set_if_neq(value);
+    }
+
+    let primary_window = primary_window.get_single().ok();
+    for (camera, mut camera_motion) in cameras.iter_mut() {
+        let Some((target, cursor)) = target else {
+            camera_motion.set_if_neq(MouseMotion::default());
+            continue;
+        };
+        let on_target = camera.target.normalize(primary_window)
+            == RenderTarget::Window(WindowRef::Entity(target)).normalize(None);
+        // If we don't know where the cursor is, all cameras on the window get the motion.
+        let contains_cursor = cursor
+            .zip(camera.logical_viewport_rect())
+            .is_none_or(|(cursor, viewport)| viewport.contains(cursor));
+        let value = if on_target && contains_cursor {
+            *motion
+        } else {
+            MouseMotion::default()
+        };
+        camera_motion.set_if_neq(value);
+    }
+}
+
+/// Resource that locks and hides the cursor within the primary window, for use with first-person controls.
+///
+/// While the cursor is locked, [`MouseMotion`] keeps tracking the movement of the mouse,
+/// but [`MousePos`](crate::MousePos) is not updated for cameras that render to the primary window.
+/// Once the lock is released, the cursor position will be brought up to date,
+/// and the cursor's previous grab mode and visibility are restored.
+#[derive(Debug, Resource, Clone, PartialEq)]
+pub struct CursorLock {
+    locked: bool,
+    /// The grab mode used while the cursor is locked. Defaults to [`CursorGrabMode::Locked`].
+    ///
+    /// Note that not all platforms support every grab mode: for example, Windows only supports [`CursorGrabMode::Confined`].
+    pub grab_mode: CursorGrabMode,
+    /// The key that releases the lock. Defaults to [`KeyCode::Escape`].
+    pub release_key: Option<KeyCode>,
+    /// Whether or not to release the lock when the primary window loses focus. Defaults to `true`.
+    pub release_on_focus_loss: bool,
+}
+
+impl Default for CursorLock {
+    fn default() -> Self {
+        Self {
+            locked: false,
+            grab_mode: CursorGrabMode::Locked,
+            release_key: Some(KeyCode::Escape),
+            release_on_focus_loss: true,
+        }
+    }
+}
+
+impl CursorLock {
+    /// Locks and hides the cursor.
+    pub fn lock(&mut self) {
+        self.locked = true;
+    }
+
+    /// Releases the cursor.
+    pub fn unlock(&mut self) {
+        self.locked = false;
+    }
+
+    /// Whether or not the cursor is currently locked.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+fn update_cursor_lock(
+    mut previous: Local<Option<(CursorGrabMode, bool)>>,
+    mut lock: ResMut<CursorLock>,
+    keys: Option<Res<Input<KeyCode>>>,
+    mut focus: EventReader<WindowFocused>,
+    mut window: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+) {
+    let Ok((window_id, mut window)) = window.get_single_mut() else {
+        return;
+    };
+
+    let release_pressed = lock
+        .release_key
+        .zip(keys)
+        .is_some_and(|(key, keys)| keys.just_pressed(key));
+    let focus_lost = focus.read().any(|e| e.window == window_id && !e.focused);
+    if lock.locked && (release_pressed || lock.release_on_focus_loss && focus_lost) {
+        lock.locked = false;
+    }
+
+    // Only touch the window when the lock changes, and put back its previous settings on release,
+    // so we don't override anything else that controls the cursor.
+    if lock.locked && previous.is_none() {
+        *previous = Some((window.cursor.grab_mode, window.cursor.visible));
+        window.cursor.grab_mode = lock.grab_mode;
+        window.cursor.visible = false;
+    } else if !lock.locked {
+        if let Some((grab_mode, visible)) = previous.take() {
+            window.cursor.grab_mode = grab_mode;
+            window.cursor.visible = visible;
+        }
+    }
+}
@@ -1,30 +1,166 @@
 use std::{fmt::Display, ops::Deref};
 
 use bevy::{
-    ecs::system::EntityCommand,
+    ecs::{
+        schedule::{InternedScheduleLabel, ScheduleLabel, SystemConfigs},
+        system::EntityCommand,
+    },
+    input::InputSystem,
     prelude::*,
-    render::camera::RenderTarget,
-    window::{PrimaryWindow, WindowRef},
+    render::camera::{CameraProjection, CameraUpdateSystem, NormalizedRenderTarget, RenderTarget},
+    transform::TransformSystem,
+    window::{PrimaryWindow, WindowRef, WindowScaleFactorChanged},
+};
+
+pub use crate::main_camera::MainCamera;
+use crate::{
+    main_camera::{
+        update_main_camera, update_resource, CurrentMainCamera, MainCameraChanged,
+        MainCameraConflict,
+    },
+    mouse_motion::CursorLock,
+    snapping::{update_tile, MouseTile, PixelSnapping},
+    window_cursor::{
+        update_desktop_cursor, update_focused_window_cursor, update_window_cursors,
+        DesktopCursorPos, FocusedWindowCursor,
+    },
 };
 
 /// Plugin that tracks the mouse location.
-pub struct MousePosPlugin;
+pub struct MousePosPlugin {
+    /// What to do when more than one entity is marked with the same [`MainCamera`](struct@MainCamera) component.
+    pub conflict_policy: MainCameraConflict,
+    /// If this is `true` and there is no entity with a [`MainCamera`](struct@MainCamera) component, the plugin will choose a main camera automatically.
+    /// Out of all cameras with mouse tracking that render to the primary window, the active one with the highest [`Camera::order`] is used.
+    pub auto_main_camera: bool,
+    /// The schedule that the [`MouseTrackingSet`]s run in. Defaults to [`PreUpdate`].
+    pub schedule: InternedScheduleLabel,
+    /// If this is `true`, the world-space tracking components and the main camera resources also get recomputed
+    /// during [`PostUpdate`], after [`TransformSystem::TransformPropagate`] and [`CameraUpdateSystem`].
+    ///
+    /// This means that [`MousePosWorld`] accounts for any camera movement that happened during the current frame,
+    /// and for any changes to its projection, such as zooming or resizing the window.
+    /// This eliminates a frame of lag for systems that run late in the frame, such as for drawing a cursor sprite.
+    pub recompute_after_propagation: bool,
+}
+
+impl Default for MousePosPlugin {
+    fn default() -> Self {
+        Self {
+            conflict_policy: default(),
+            auto_main_camera: false,
+            schedule: PreUpdate.intern(),
+            recompute_after_propagation: false,
+        }
+    }
+}
+
+/// The system sets that update the mouse tracking components and resources.
+/// These run in the order they are listed in, within the schedule given by [`MousePosPlugin::schedule`].
+///
+/// By default, they run during [`PreUpdate`] after [`InputSystem`], so any system in [`Update`] will see up-to-date values.
+/// Note that this is before the transform propagation for the current frame,
+/// so the world-space position uses the camera's [`GlobalTransform`] as of the end of the previous frame.
+/// If that is a problem, see [`MousePosPlugin::recompute_after_propagation`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseTrackingSet {
+    /// Updates the screen-space tracking components:
+    /// [`MousePos`], [`MousePosPhysical`], [`MousePosNdc`], and [`WindowCursor`](crate::WindowCursor).
+    Screen,
+    /// Updates the world-space tracking components: [`MousePosWorld`] and [`MouseTile`].
+    World,
+    /// Updates the global resources for the [`MainCamera`](struct@MainCamera).
+    Resources,
+}
+
+/// The schedules that mouse tracking happens in.
+#[derive(Resource, Clone, Copy)]
+pub(crate) struct TrackingSchedule {
+    pub schedule: InternedScheduleLabel,
+    pub recompute_after_propagation: bool,
+}
 
 impl Plugin for MousePosPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(MousePos(default()))
+        app.insert_resource(self.conflict_policy)
+            .insert_resource(TrackingSchedule {
+                schedule: self.schedule,
+                recompute_after_propagation: self.recompute_after_propagation,
+            })
+            .insert_resource(CurrentMainCamera::<()>::new(self.auto_main_camera))
+            .add_event::<MainCameraChanged>()
+            .insert_resource(MousePos(default()))
             .insert_resource(MousePosWorld(default()))
+            .insert_resource(MousePosPhysical(default()))
+            .insert_resource(MousePosNdc(default()))
+            .init_resource::<MouseTile>()
+            .init_resource::<FocusedWindowCursor>()
+            .init_resource::<DesktopCursorPos>()
+            .configure_sets(
+                self.schedule,
+                (
+                    MouseTrackingSet::Screen,
+                    MouseTrackingSet::World,
+                    MouseTrackingSet::Resources,
+                )
+                    .chain()
+                    .after(InputSystem),
+            )
             .add_systems(
-                Update,
-                (update_pos, update_pos_ortho, update_resources).chain(),
+                self.schedule,
+                (
+                    (update_pos, (update_pos_physical, update_pos_ndc))
+                        .chain()
+                        .in_set(MouseTrackingSet::Screen),
+                    (
+                        update_window_cursors,
+                        (update_focused_window_cursor, update_desktop_cursor),
+                    )
+                        .chain()
+                        .in_set(MouseTrackingSet::Screen),
+                    world_systems(),
+                    resource_systems(),
+                ),
             );
+
+        if self.recompute_after_propagation {
+            app.configure_sets(
+                PostUpdate,
+                (MouseTrackingSet::World, MouseTrackingSet::Resources)
+                    .chain()
+                    .after(TransformSystem::TransformPropagate)
+                    .after(CameraUpdateSystem),
+            )
+            .add_systems(PostUpdate, (world_systems(), resource_systems()));
+        }
     }
 }
 
+fn world_systems() -> SystemConfigs {
+    (update_pos_ortho, update_tile)
+        .chain()
+        .in_set(MouseTrackingSet::World)
+}
+
+fn resource_systems() -> SystemConfigs {
+    (
+        update_main_camera::<()>,
+        (
+            update_resource::<MousePos>,
+            update_resource::<MousePosWorld>,
+            update_resource::<MousePosPhysical>,
+            update_resource::<MousePosNdc>,
+            update_resource::<MouseTile>,
+        ),
+    )
+        .chain()
+        .in_set(MouseTrackingSet::Resources)
+}
+
 /// The location of the mouse in screenspace.  
-/// This will be updated every frame during [`CoreStage::First`]. Any systems that rely
-/// on this should come after `CoreStage::First`.
-#[derive(Debug, Resource, Clone, Copy, PartialEq, Component)]
+/// This will be updated every frame during [`MouseTrackingSet::Screen`]. Any systems that rely
+/// on this should come after that set.
+#[derive(Debug, Default, Resource, Clone, Copy, PartialEq, Component)]
 pub struct MousePos(Vec2);
 
 impl Deref for MousePos {
@@ -41,7 +177,7 @@ impl Display for MousePos {
     }
 }
 
-/// A [`Command`] that adds the [`MousePos`] component to a [`Camera`], ensuring that the initial cursor position is correct.
+/// An [`EntityCommand`] that adds the [`MousePos`] component to a [`Camera`], ensuring that the initial cursor position is correct.
 ///
 /// The simplest way to enable mouse tracking for a camera is to add the component `MousePos::default`
 /// -- however, doing this means that the initial value for the cursor position will be zero. This command handles that automatically.
@@ -51,76 +187,238 @@ pub struct InitMouseTracking;
 
 impl EntityCommand for InitMouseTracking {
     fn apply(self, entity: Entity, world: &mut World) {
-        #[track_caller]
-        #[cold]
-        fn no_camera(id: impl std::fmt::Debug) -> ! {
-            panic!("tried to call the command `InitMouseTracking` on non-camera entity '{id:?}'")
-        }
-        #[track_caller]
-        #[cold]
-        fn image_camera(id: impl std::fmt::Debug) -> ! {
-            panic!(
-                "tried to call the command `InitMouseTracking` on a camera ({id:?}) that renders to an image",
-            )
-        }
-        #[track_caller]
-        #[cold]
-        fn no_window(id: impl std::fmt::Debug) -> ! {
-            panic!("could not find the window '{id:?}'")
-        }
-
-        let primary_window = world
-            .query_filtered::<Entity, With<PrimaryWindow>>()
-            .get_single(world)
-            .ok();
-
-        let camera = world
-            .entity(entity)
-            .get::<Camera>()
-            .unwrap_or_else(|| no_camera(entity));
-        let RenderTarget::Window(window_id) = camera.target else {
-            image_camera(entity);
-        };
-        let window_id = window_id
-            .normalize(primary_window)
-            .expect("`PrimaryWindow` does not exist")
-            .entity();
-
-        let window = world
-            .query::<&Window>()
-            .get(world, window_id)
-            .unwrap_or_else(|_| no_window(window_id));
+        let window_id = camera_window(entity, world);
+        let window = world.get::<Window>(window_id).unwrap();
         let mouse_pos = window.cursor_position().unwrap_or_default();
 
         world.entity_mut(entity).insert(MousePos(mouse_pos));
     }
 }
 
+/// Finds the window that the specified camera renders to.
+#[track_caller]
+fn camera_window(entity: Entity, world: &mut World) -> Entity {
+    #[track_caller]
+    #[cold]
+    fn no_camera(id: impl std::fmt::Debug) -> ! {
+        panic!("tried to call the command `InitMouseTracking` on non-camera entity '{id:?}'")
+    }
+    #[track_caller]
+    #[cold]
+    fn image_camera(id: impl std::fmt::Debug) -> ! {
+        panic!(
+            "tried to call the command `InitMouseTracking` on a camera ({id:?}) that renders to an image",
+        )
+    }
+    #[track_caller]
+    #[cold]
+    fn no_window(id: impl std::fmt::Debug) -> ! {
+        panic!("could not find the window '{id:?}'")
+    }
+
+    let primary_window = world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .get_single(world)
+        .ok();
+
+    let camera = world
+        .entity(entity)
+        .get::<Camera>()
+        .unwrap_or_else(|| no_camera(entity));
+    let RenderTarget::Window(window_id) = camera.target else {
+        image_camera(entity);
+    };
+    let window_id = window_id
+        .normalize(primary_window)
+        .expect("`PrimaryWindow` does not exist")
+        .entity();
+
+    if world.get::<Window>(window_id).is_none() {
+        no_window(window_id);
+    }
+    window_id
+}
+
 fn update_pos(
     mut movement: EventReader<CursorMoved>,
     mut cameras: Query<(&Camera, &mut MousePos)>,
     primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    cursor_lock: Option<Res<CursorLock>>,
 ) {
     let primary_window = primary_window.get_single().ok();
+    let primary_target = RenderTarget::Window(WindowRef::Primary).normalize(primary_window);
+    let locked = cursor_lock.as_ref().is_some_and(|lock| lock.is_locked());
+
+    // If the cursor just got unlocked, catch up on any movement that was ignored.
+    if cursor_lock.is_some_and(|lock| lock.is_changed() && !lock.is_locked()) {
+        if let Some(position) = primary_window
+            .and_then(|w| windows.get(w).ok())
+            .and_then(Window::cursor_position)
+        {
+            for (_, mut pos) in cameras
+                .iter_mut()
+                .filter(|(c, ..)| c.target.normalize(primary_window) == primary_target)
+            {
+                pos.set_if_neq(MousePos(position));
+            }
+        }
+    }
+
     for &CursorMoved {
         window, position, ..
     } in movement.read()
     {
+        // While the cursor is locked, its position in the primary window is meaningless.
+        if locked && Some(window) == primary_window {
+            continue;
+        }
         let target = RenderTarget::Window(WindowRef::Entity(window)).normalize(None);
         // find all cameras corresponding to the window on which the cursor moved.
         for (_, mut pos) in cameras
             .iter_mut()
             .filter(|(c, ..)| c.target.normalize(primary_window) == target)
         {
-            pos.0 = position;
+            pos.set_if_neq(MousePos(position));
+        }
+    }
+}
+
+/// The location of the mouse in screenspace, measured in physical pixels.
+///
+/// This is the same as [`MousePos`], multiplied by the window's scale factor.
+/// It is useful for pixel-perfect rendering at integer physical resolutions, such as for pixel-art games.
+#[derive(Debug, Default, Resource, Clone, Copy, PartialEq, Component)]
+pub struct MousePosPhysical(Vec2);
+
+impl Deref for MousePosPhysical {
+    type Target = Vec2;
+
+    fn deref(&self) -> &Vec2 {
+        &self.0
+    }
+}
+
+impl Display for MousePosPhysical {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An [`EntityCommand`] that adds the component [`MousePosPhysical`] to an entity, with a correct initial position.
+/// For more details, see the docs for [`InitMouseTracking`].
+///
+/// Executing this command automatically executes `InitMouseTracking`.
+pub struct InitPhysicalTracking;
+
+impl EntityCommand for InitPhysicalTracking {
+    fn apply(self, entity: Entity, world: &mut World) {
+        InitMouseTracking.apply(entity, world);
+
+        let window_id = camera_window(entity, world);
+        let scale_factor = world.get::<Window>(window_id).unwrap().scale_factor();
+        let screen_pos = world.get::<MousePos>(entity).unwrap();
+        let physical_pos = screen_pos.0 * scale_factor as f32;
+        world
+            .entity_mut(entity)
+            .insert(MousePosPhysical(physical_pos));
+    }
+}
+
+fn update_pos_physical(
+    mut scale_changed: EventReader<WindowScaleFactorChanged>,
+    mut tracking: Query<(&Camera, Ref<MousePos>, &mut MousePosPhysical)>,
+    windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    let primary_window = primary_window.get_single().ok();
+    let changed_windows: Vec<_> = scale_changed.read().map(|e| e.window).collect();
+    for (camera, screen, mut physical) in tracking.iter_mut() {
+        let Some(NormalizedRenderTarget::Window(window_id)) =
+            camera.target.normalize(primary_window)
+        else {
+            continue;
+        };
+        let window_id = window_id.entity();
+        if !screen.is_changed() && !changed_windows.contains(&window_id) {
+            continue;
+        }
+        let Ok(window) = windows.get(window_id) else {
+            continue;
+        };
+        physical.set_if_neq(MousePosPhysical(screen.0 * window.scale_factor() as f32));
+    }
+}
+
+/// The location of the mouse in the normalized device coordinates of a camera's viewport.
+///
+/// Both axes range from `-1.0` to `1.0`, with `(-1, -1)` in the bottom left corner of the viewport.
+/// This is useful for passing the cursor position to shaders and post-processing effects.
+/// See [`MousePosNdc::uv`] for the position in texture coordinates.
+#[derive(Debug, Default, Resource, Clone, Copy, PartialEq, Component)]
+pub struct MousePosNdc(Vec2);
+
+impl MousePosNdc {
+    /// The location of the mouse in UV coordinates.
+    /// Both axes range from `0.0` to `1.0`, with `(0, 0)` in the top left corner of the viewport.
+    pub fn uv(self) -> Vec2 {
+        Vec2::new(self.0.x + 1.0, 1.0 - self.0.y) * 0.5
+    }
+}
+
+impl Deref for MousePosNdc {
+    type Target = Vec2;
+
+    fn deref(&self) -> &Vec2 {
+        &self.0
+    }
+}
+
+impl Display for MousePosNdc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An [`EntityCommand`] that adds the component [`MousePosNdc`] to an entity, with a correct initial position.
+/// For more details, see the docs for [`InitMouseTracking`].
+///
+/// Executing this command automatically executes `InitMouseTracking`.
+pub struct InitNdcTracking;
+
+impl EntityCommand for InitNdcTracking {
+    fn apply(self, entity: Entity, world: &mut World) {
+        InitMouseTracking.apply(entity, world);
+
+        let mut entity_mut = world.entity_mut(entity);
+        let screen_pos = entity_mut.get::<MousePos>().unwrap();
+        let camera = entity_mut.get::<Camera>().unwrap();
+        // If bevy hasn't computed the size of the viewport yet, this will get filled in once it does.
+        let ndc = camera
+            .logical_viewport_rect()
+            .map(|viewport| viewport_to_ndc(screen_pos.0, viewport))
+            .unwrap_or_default();
+        entity_mut.insert(MousePosNdc(ndc));
+    }
+}
+
+fn update_pos_ndc(
+    mut tracking: Query<
+        (&Camera, &MousePos, &mut MousePosNdc),
+        Or<(Changed<MousePos>, Changed<Camera>)>,
+    >,
+) {
+    for (camera, screen, mut ndc) in tracking.iter_mut() {
+        if let Some(viewport) = camera.logical_viewport_rect() {
+            ndc.set_if_neq(MousePosNdc(viewport_to_ndc(screen.0, viewport)));
         }
     }
 }
 
 /// The location of the mouse in worldspace.  
-/// This will be updated every frame during [`CoreStage::First`]. Any systems that rely
-/// on this should come after `CoreStage::First`.
-#[derive(Debug, Resource, Clone, Copy, PartialEq, Component)]
+/// This will be updated every frame during [`MouseTrackingSet::World`]. Any systems that rely
+/// on this should come after that set.
+#[derive(Debug, Default, Resource, Clone, Copy, PartialEq, Component)]
 pub struct MousePosWorld(Vec3);
 
 impl Display for MousePosWorld {
@@ -137,7 +435,7 @@ impl Deref for MousePosWorld {
     }
 }
 
-/// A [`Command`] that adds the component [`MousePosWorld`] to an entity, with a correct initial position.
+/// An [`EntityCommand`] that adds the component [`MousePosWorld`] to an entity, with a correct initial position.
 /// For more details, see the docs for [`InitMouseTracking`].
 ///
 /// Executing this command automatically executes `InitMouseTracking`.
@@ -157,89 +455,110 @@ impl EntityCommand for InitWorldTracking {
         let mut entity_mut = world.entity_mut(entity);
 
         let screen_pos = entity_mut.get::<MousePos>().unwrap();
+        let camera = entity_mut.get::<Camera>().unwrap();
         let &transform = entity_mut
             .get::<GlobalTransform>()
             .unwrap_or_else(|| no_transform(entity));
         let proj = entity_mut
             .get::<OrthographicProjection>()
             .unwrap_or_else(|| no_proj(entity));
-        let world_pos = compute_world_pos_ortho(screen_pos.0, transform, proj);
+        let snapping = entity_mut.get::<PixelSnapping>();
+        let world_pos = compute_world_pos_ortho(screen_pos.0, camera, transform, proj, snapping);
         entity_mut.insert(MousePosWorld(world_pos));
     }
 }
 
 fn update_pos_ortho(
     mut tracking: Query<
-        (Entity, &mut MousePosWorld, &MousePos),
-        Or<(Changed<MousePos>, Changed<GlobalTransform>)>,
+        (
+            Entity,
+            &mut MousePosWorld,
+            &MousePos,
+            Option<&PixelSnapping>,
+        ),
+        // Bevy updates the `Camera` and its projection whenever the window gets resized,
+        // so this also covers window resize events.
+        Or<(
+            Changed<MousePos>,
+            Changed<GlobalTransform>,
+            Changed<PixelSnapping>,
+            Changed<OrthographicProjection>,
+            Changed<Camera>,
+        )>,
     >,
-    cameras: Query<(&GlobalTransform, &OrthographicProjection)>,
+    cameras: Query<(&Camera, &GlobalTransform, &OrthographicProjection)>,
 ) {
-    for (camera, mut world, screen) in tracking.iter_mut() {
-        let (&camera, proj) = cameras
+    for (camera, mut world, screen, snapping) in tracking.iter_mut() {
+        let (camera, &transform, proj) = cameras
             .get(camera)
             .expect("only orthographic cameras are supported");
-        world.0 = compute_world_pos_ortho(screen.0, camera, proj);
+        world.set_if_neq(MousePosWorld(compute_world_pos_ortho(
+            screen.0, camera, transform, proj, snapping,
+        )));
     }
 }
 
-fn compute_world_pos_ortho(
+/// Converts a window-space cursor position into world-space for an orthographic camera.
+///
+/// The conversion goes through the inverse of the camera's projection matrix, so it works for
+/// every [`ScalingMode`](bevy::render::camera::ScalingMode) and `viewport_origin`.
+/// Rotation and non-uniform scaling of the camera are handled by its [`GlobalTransform`].
+/// If [`PixelSnapping`] is specified, the position gets snapped to the grid of pixels, as described in its docs.
+pub(crate) fn compute_world_pos_ortho(
     screen_pos: Vec2,
+    camera: &Camera,
     transform: GlobalTransform,
     proj: &OrthographicProjection,
+    snapping: Option<&PixelSnapping>,
 ) -> Vec3 {
-    let offset = Vec2::new(proj.area.min.x, proj.area.max.y) / proj.scale;
-    // Must multiply by projection scale before applying camera global transform
-    // Otherwise you get weird offset mouse positions when both scaling and panning the camera.
-    transform * ((screen_pos * Vec2::new(1.0, -1.0) + offset) * proj.scale).extend(0.0)
-}
-
-/// Marker component for the main camera. If no main camera is specified, all cameras will be treated equally.
-#[derive(Component)]
-pub struct MainCamera;
-
-fn update_resources(
-    mut last_main: Local<Option<Entity>>,
-    added_main: Query<Entity, Added<MainCamera>>,
-    mut removed_main: RemovedComponents<MainCamera>,
-    mut screen_res: ResMut<MousePos>,
-    mut world_res: ResMut<MousePosWorld>,
-    screen: Query<&MousePos>,
-    world: Query<&MousePosWorld>,
-) {
-    // List of all entities known to have the MainCamera marker.
-    // This includes the main camera from last frame, and all entities with the component added this frame.
-    let mut with_marker: Vec<_> = Option::into_iter(*last_main).chain(&added_main).collect();
-    // Ditch any removed components.
-    for rem in removed_main.read() {
-        if let Some(idx) = with_marker.iter().position(|&x| x == rem) {
-            with_marker.remove(idx);
-        }
-    }
-    match *with_marker {
-        // If there is only one main camera, update the resources using it.
-        [main] => {
-            *last_main = Some(main);
-            let screen = screen.get(main).map_or_else(|_| default(), |s| s.0);
-            if screen_res.0 != screen {
-                screen_res.0 = screen;
-            }
-            let world = world.get(main).map_or_else(|_| default(), |w| w.0);
-            if world_res.0 != world {
-                world_res.0 = world;
-            }
-        }
-        // If there is no main camera, zero out the resources.
-        [] => {
-            if last_main.is_some() {
-                *last_main = None;
-                *screen_res = MousePos(default());
-                *world_res = MousePosWorld(default());
-            }
-        }
-        // Panic if there is more than one main camera.
-        [..] => {
-            panic!("`bevy_mouse_tracking_plugin`: there cannot be more than one entity with a `MainCamera` component");
-        }
+    let local = compute_local_pos_ortho(screen_pos, camera, proj);
+    let Some(&PixelSnapping(rounding)) = snapping else {
+        return transform * local.extend(0.0);
+    };
+    let pixel_size = pixel_size_ortho(camera, proj);
+    let axes = transform.affine().matrix3;
+    if axes.x_axis.y == 0.0 && axes.y_axis.x == 0.0 {
+        // The camera isn't rotated, so its pixels line up with the world axes.
+        let world = transform * local.extend(0.0);
+        let cell_size = pixel_size * Vec2::new(axes.x_axis.x, axes.y_axis.y).abs();
+        rounding.snap(world.truncate(), cell_size).extend(world.z)
+    } else {
+        // A rotated grid of pixels can't line up with the world axes, so snap relative to the camera instead.
+        transform * rounding.snap(local, pixel_size).extend(0.0)
     }
 }
+
+/// Computes the position of the cursor on the camera's plane, relative to the camera.
+pub(crate) fn compute_local_pos_ortho(
+    screen_pos: Vec2,
+    camera: &Camera,
+    proj: &OrthographicProjection,
+) -> Vec2 {
+    let viewport = logical_viewport(camera, proj);
+    let ndc = viewport_to_ndc(screen_pos, viewport);
+    // Unproject onto the near plane, and then flatten it onto the camera's plane.
+    proj.get_projection_matrix()
+        .inverse()
+        .project_point3(ndc.extend(1.0))
+        .truncate()
+}
+
+/// The size of a single logical pixel, in the camera's local units.
+pub(crate) fn pixel_size_ortho(camera: &Camera, proj: &OrthographicProjection) -> Vec2 {
+    proj.area.size() / logical_viewport(camera, proj).size()
+}
+
+fn logical_viewport(camera: &Camera, proj: &OrthographicProjection) -> Rect {
+    // If the camera hasn't been set up by bevy yet, we can't know the size of its viewport.
+    // Assume that each logical pixel corresponds to `scale` world units, which is correct for the default projection.
+    camera
+        .logical_viewport_rect()
+        .unwrap_or_else(|| Rect::from_corners(Vec2::ZERO, proj.area.size() / proj.scale))
+}
+
+/// Converts a window-space position into normalized device coordinates for the given viewport.
+fn viewport_to_ndc(screen_pos: Vec2, viewport: Rect) -> Vec2 {
+    let uv = (screen_pos - viewport.min) / viewport.size();
+    // Window-space has its origin in the top left, while NDC has +Y pointing up.
+    Vec2::new(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0)
+}
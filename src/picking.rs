@@ -0,0 +1,366 @@
+//! 2D picking, for finding the entities under the mouse.
+//!
+//! After adding [`PickingPlugin`], every camera that tracks the world-space mouse position
+//! gets a [`HoveredEntities`] component, listing the entities under the mouse from front to back.
+//! The bounds of an entity are taken from, in order of preference:
+//!
+//! * its [`PickShape`] component, if it has one;
+//! * the size of its `Sprite`, if the `sprite` feature is enabled;
+//! * its [`Aabb`], which bevy computes for sprites and meshes.
+//!
+//! [`PointerOver`] and [`PointerOut`] events are sent whenever an entity starts or stops being hovered.
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_mouse_tracking_plugin::picking::{HoveredEntities, PickShape};
+//!
+//! fn spawn_button(mut commands: Commands) {
+//!     commands.spawn((SpatialBundle::default(), PickShape::Circle { radius: 16.0 }));
+//! }
+//!
+//! fn print_hovered(cameras: Query<&HoveredEntities>, names: Query<&Name>) {
+//!     for hovered in &cameras {
+//!         if let Some(name) = hovered.top().and_then(|e| names.get(e).ok()) {
+//!             println!("The mouse is over {name}");
+//!         }
+//!     }
+//! }
+//! # bevy::ecs::system::assert_is_system(spawn_button);
+//! # bevy::ecs::system::assert_is_system(print_hovered);
+//! ```
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::NormalizedRenderTarget,
+        primitives::Aabb,
+        view::{RenderLayers, VisibilitySystems},
+    },
+    transform::TransformSystem,
+    window::PrimaryWindow,
+};
+
+use crate::{mouse_pos::MouseTrackingSet, MousePos, MousePosWorld};
+
+/// Plugin that finds the entities under the mouse, for every camera that tracks the world-space mouse position.
+pub struct PickingPlugin;
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PointerOver>();
+        app.add_event::<PointerOut>();
+        app.configure_sets(
+            PostUpdate,
+            PickingSet
+                .after(TransformSystem::TransformPropagate)
+                .after(VisibilitySystems::CalculateBoundsFlush)
+                // Tracking may be recomputed during `PostUpdate`, so make sure to see this frame's position.
+                .after(MouseTrackingSet::Resources),
+        );
+        app.add_systems(PostUpdate, update_hovered_entities.in_set(PickingSet));
+        #[cfg(feature = "sprite")]
+        app.add_systems(
+            PostUpdate,
+            update_sprite_bounds.in_set(VisibilitySystems::CalculateBounds),
+        );
+    }
+}
+
+/// System set containing the systems that update [`HoveredEntities`].
+/// Runs in [`PostUpdate`], after transforms have been propagated and after any [`MouseTrackingSet`] in that schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
+pub struct PickingSet;
+
+/// A shape that can be hovered by the mouse, relative to the entity's origin.
+#[derive(Debug, Clone, PartialEq, Component)]
+pub enum PickShape {
+    /// A circle centered on the entity's origin.
+    Circle { radius: f32 },
+    /// A rectangle centered on the entity's origin.
+    Rect { half_size: Vec2 },
+    /// A polygon with the specified vertices.
+    /// Self-intersecting polygons are filled according to the even-odd rule.
+    Polygon(Vec<Vec2>),
+}
+
+impl PickShape {
+    /// Checks whether the shape contains a point, relative to the entity's origin.
+    pub fn contains(&self, point: Vec2) -> bool {
+        match self {
+            Self::Circle { radius } => point.length_squared() <= radius * radius,
+            Self::Rect { half_size } => point.abs().cmple(*half_size).all(),
+            Self::Polygon(vertices) => polygon_contains(vertices, point),
+        }
+    }
+
+    /// The smallest rectangle that contains the shape.
+    pub fn bounding_rect(&self) -> Rect {
+        match self {
+            Self::Circle { radius } => {
+                Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(*radius))
+            }
+            Self::Rect { half_size } => Rect::from_center_half_size(Vec2::ZERO, *half_size),
+            Self::Polygon(vertices) => Rect {
+                min: vertices.iter().fold(Vec2::INFINITY, |min, &v| min.min(v)),
+                max: vertices
+                    .iter()
+                    .fold(Vec2::NEG_INFINITY, |max, &v| max.max(v)),
+            },
+        }
+    }
+}
+
+/// Checks whether a polygon contains a point, according to the even-odd rule.
+pub(crate) fn polygon_contains(vertices: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+    let mut prev = match vertices.last() {
+        Some(&last) => last,
+        None => return false,
+    };
+    for &vertex in vertices {
+        // Count the edges crossing a ray from the point towards +X.
+        if (vertex.y > point.y) != (prev.y > point.y) {
+            let t = (point.y - vertex.y) / (prev.y - vertex.y);
+            if point.x < vertex.x + t * (prev.x - vertex.x) {
+                inside = !inside;
+            }
+        }
+        prev = vertex;
+    }
+    inside
+}
+
+/// Marker component for entities that should never be hovered, even though they have bounds.
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub struct IgnorePicking;
+
+/// The bounds of a sprite, relative to the entity's origin.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub(crate) struct SpriteBounds(Rect);
+
+/// The entities underneath the mouse for a camera, ordered from front to back.
+///
+/// This gets added to every camera that tracks the world-space mouse position.
+/// It is empty while the mouse is outside of the camera's viewport.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Component)]
+pub struct HoveredEntities(Vec<Entity>);
+
+impl HoveredEntities {
+    /// The entity in front, if any.
+    pub fn top(&self) -> Option<Entity> {
+        self.0.first().copied()
+    }
+
+    /// The hovered entities, ordered from front to back.
+    pub fn entities(&self) -> &[Entity] {
+        &self.0
+    }
+
+    /// Checks whether the mouse is over the specified entity.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(&entity)
+    }
+}
+
+/// Event sent when the mouse starts hovering over an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct PointerOver {
+    /// The camera that the entity is being viewed through.
+    pub camera: Entity,
+    /// The entity that is now hovered.
+    pub entity: Entity,
+}
+
+/// Event sent when the mouse stops hovering over an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct PointerOut {
+    /// The camera that the entity was being viewed through.
+    pub camera: Entity,
+    /// The entity that is no longer hovered.
+    pub entity: Entity,
+}
+
+/// Checks whether the cursor is inside of the window and viewport of an active camera.
+pub(crate) fn cursor_in_viewport(
+    camera: &Camera,
+    mouse_pos: MousePos,
+    windows: &Query<&Window>,
+    primary_window: Option<Entity>,
+) -> bool {
+    let cursor_present = match camera.target.normalize(primary_window) {
+        Some(NormalizedRenderTarget::Window(window)) => windows
+            .get(window.entity())
+            .is_ok_and(|window| window.cursor_position().is_some()),
+        _ => false,
+    };
+    let in_viewport = camera
+        .logical_viewport_rect()
+        .is_none_or(|viewport| viewport.contains(*mouse_pos));
+    camera.is_active && cursor_present && in_viewport
+}
+
+/// Finds the camera that the cursor is pointing at, preferring the one that renders last.
+pub(crate) fn camera_under_cursor<'a>(
+    cameras: impl Iterator<Item = (Entity, &'a Camera, &'a MousePos)>,
+    windows: &Query<&Window>,
+    primary_window: Option<Entity>,
+) -> Option<Entity> {
+    cameras
+        .filter(|&(_, camera, &mouse_pos)| {
+            cursor_in_viewport(camera, mouse_pos, windows, primary_window)
+        })
+        .max_by_key(|(_, camera, _)| camera.order)
+        .map(|(entity, ..)| entity)
+}
+
+/// The bounding rectangle of an entity, relative to its origin.
+pub(crate) fn local_bounds(
+    shape: Option<&PickShape>,
+    sprite: Option<&SpriteBounds>,
+    aabb: Option<&Aabb>,
+) -> Option<Rect> {
+    match (shape, sprite, aabb) {
+        (Some(shape), ..) => Some(shape.bounding_rect()),
+        (None, Some(&SpriteBounds(rect)), _) => Some(rect),
+        (None, None, Some(aabb)) => Some(Rect::from_center_half_size(
+            aabb.center.truncate(),
+            aabb.half_extents.truncate(),
+        )),
+        (None, None, None) => None,
+    }
+}
+
+/// Checks whether a world-space position lies within the bounds of an entity, ignoring depth.
+fn bounds_contain(
+    transform: &GlobalTransform,
+    shape: Option<&PickShape>,
+    sprite: Option<&SpriteBounds>,
+    aabb: Option<&Aabb>,
+    world_pos: Vec2,
+) -> bool {
+    let point = world_pos.extend(transform.translation().z);
+    let local = transform.affine().inverse().transform_point3(point);
+    match (shape, sprite, aabb) {
+        (Some(shape), ..) => shape.contains(local.truncate()),
+        (None, Some(SpriteBounds(rect)), _) => rect.contains(local.truncate()),
+        (None, None, Some(aabb)) => {
+            let offset = (local - Vec3::from(aabb.center)).truncate().abs();
+            offset.cmple(Vec3::from(aabb.half_extents).truncate()).all()
+        }
+        (None, None, None) => false,
+    }
+}
+
+#[cfg(feature = "sprite")]
+fn update_sprite_bounds(
+    mut commands: Commands,
+    mut sprites: Query<(
+        Entity,
+        &bevy::sprite::Sprite,
+        &Handle<Image>,
+        Option<&mut SpriteBounds>,
+    )>,
+    images: Res<Assets<Image>>,
+) {
+    for (entity, sprite, image, bounds) in &mut sprites {
+        let size = sprite
+            .custom_size
+            .or_else(|| sprite.rect.map(|rect| rect.size()))
+            .or_else(|| images.get(image).map(|image| image.size_f32()));
+        // Wait until the image is loaded.
+        let Some(size) = size else {
+            continue;
+        };
+        let rect = Rect::from_center_size(-sprite.anchor.as_vec() * size, size);
+        match bounds {
+            Some(mut bounds) => {
+                bounds.set_if_neq(SpriteBounds(rect));
+            }
+            None => {
+                commands.entity(entity).insert(SpriteBounds(rect));
+            }
+        }
+    }
+}
+
+fn update_hovered_entities(
+    mut commands: Commands,
+    mut cameras: Query<(
+        Entity,
+        &Camera,
+        &MousePos,
+        &MousePosWorld,
+        Option<&RenderLayers>,
+        Option<&mut HoveredEntities>,
+    )>,
+    targets: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            Option<&PickShape>,
+            Option<&SpriteBounds>,
+            Option<&Aabb>,
+            Option<&InheritedVisibility>,
+            Option<&RenderLayers>,
+        ),
+        (
+            Or<(With<PickShape>, With<SpriteBounds>, With<Aabb>)>,
+            Without<IgnorePicking>,
+        ),
+    >,
+    windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut over_events: EventWriter<PointerOver>,
+    mut out_events: EventWriter<PointerOut>,
+) {
+    let primary_window = primary_window.get_single().ok();
+
+    for (camera_entity, camera, &mouse_pos, &mouse_pos_world, camera_layers, hovered) in
+        &mut cameras
+    {
+        let mut new_hovered = Vec::new();
+        if cursor_in_viewport(camera, mouse_pos, &windows, primary_window) {
+            let camera_layers = camera_layers.copied().unwrap_or_default();
+            let mut hits: Vec<_> = targets
+                .iter()
+                .filter(|(.., visibility, _)| visibility.is_none_or(|v| v.get()))
+                .filter(|(.., layers)| {
+                    camera_layers.intersects(&layers.copied().unwrap_or_default())
+                })
+                .filter(|&(_, transform, shape, sprite, aabb, ..)| {
+                    bounds_contain(transform, shape, sprite, aabb, mouse_pos_world.truncate())
+                })
+                .map(|(entity, transform, ..)| (entity, transform.translation().z))
+                .collect();
+            // Sort from front to back.
+            hits.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+            new_hovered.extend(hits.into_iter().map(|(entity, _)| entity));
+        }
+        let new_hovered = HoveredEntities(new_hovered);
+
+        let old_hovered = hovered.as_deref().map_or(&[][..], |h| h.entities());
+        let camera = camera_entity;
+        out_events.send_batch(
+            old_hovered
+                .iter()
+                .filter(|&&entity| !new_hovered.contains(entity))
+                .map(|&entity| PointerOut { camera, entity }),
+        );
+        over_events.send_batch(
+            new_hovered
+                .0
+                .iter()
+                .filter(|&&entity| !old_hovered.contains(&entity))
+                .map(|&entity| PointerOver { camera, entity }),
+        );
+
+        match hovered {
+            Some(mut hovered) => {
+                hovered.set_if_neq(new_hovered);
+            }
+            None => {
+                commands.entity(camera_entity).insert(new_hovered);
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Mouse events targeted at individual entities, such as hovering and clicking.
+//!
+//! After adding [`PointerEventsPlugin`], entities with a [`Pickable`] component will receive
+//! [`CursorEnter`], [`CursorExit`], [`CursorDown`], [`CursorUp`] and [`CursorClick`] events.
+//! The target of each event is the frontmost entity under the mouse, as found by the [`picking`](crate::picking) module.
+//! If several cameras overlap under the mouse, only the one that renders last sends events.
+//!
+//! Events bubble up the [`Parent`] hierarchy: the target and each of its ancestors that is [`Pickable`]
+//! receives a copy of the event, with the `listener` field set to that entity. This means that a
+//! [`Pickable`] parent gets events for the child sprites that make up its visible parts.
+//! Bubbling stops at any entity with [`Pickable::stop_propagation`] set.
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_mouse_tracking_plugin::pointer_events::CursorClick;
+//!
+//! #[derive(Component)]
+//! struct Button;
+//!
+//! fn click_buttons(mut clicks: EventReader<CursorClick>, buttons: Query<&Name, With<Button>>) {
+//!     for click in clicks.read() {
+//!         if let Ok(name) = buttons.get(click.listener) {
+//!             println!("Clicked {name} with {:?}", click.button);
+//!         }
+//!     }
+//! }
+//! # bevy::ecs::system::assert_is_system(click_buttons);
+//! ```
+
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap, window::PrimaryWindow};
+
+use crate::{
+    picking::{camera_under_cursor, HoveredEntities, PickingPlugin, PickingSet},
+    MousePos,
+};
+
+/// Plugin that sends mouse events to [`Pickable`] entities.
+///
+/// This adds [`PickingPlugin`] if it hasn't been added already.
+pub struct PointerEventsPlugin;
+
+impl Plugin for PointerEventsPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<PickingPlugin>() {
+            app.add_plugins(PickingPlugin);
+        }
+        app.add_event::<CursorEnter>()
+            .add_event::<CursorExit>()
+            .add_event::<CursorDown>()
+            .add_event::<CursorUp>()
+            .add_event::<CursorClick>();
+        app.add_systems(PostUpdate, send_pointer_events.after(PickingSet));
+    }
+}
+
+/// Component that allows an entity to receive mouse events.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Pickable {
+    /// If this is `true`, no event from this entity or its descendants bubbles up past it.
+    pub stop_propagation: bool,
+}
+
+/// Event sent when the mouse moves onto an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct CursorEnter {
+    /// The entity receiving this event, which is either the target or one of its ancestors.
+    pub listener: Entity,
+    /// The entity that the mouse moved onto.
+    pub target: Entity,
+    /// The camera that the entity is being viewed through.
+    pub camera: Entity,
+}
+
+/// Event sent when the mouse moves off of an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct CursorExit {
+    /// The entity receiving this event, which is either the target or one of its ancestors.
+    pub listener: Entity,
+    /// The entity that the mouse moved off of.
+    pub target: Entity,
+    /// The camera that the entity was being viewed through.
+    pub camera: Entity,
+}
+
+/// Event sent when a mouse button is pressed over an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct CursorDown {
+    /// The entity receiving this event, which is either the target or one of its ancestors.
+    pub listener: Entity,
+    /// The entity under the mouse.
+    pub target: Entity,
+    /// The camera that the entity is being viewed through.
+    pub camera: Entity,
+    /// The mouse button that was pressed.
+    pub button: MouseButton,
+}
+
+/// Event sent when a mouse button is released over an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct CursorUp {
+    /// The entity receiving this event, which is either the target or one of its ancestors.
+    pub listener: Entity,
+    /// The entity under the mouse.
+    pub target: Entity,
+    /// The camera that the entity is being viewed through.
+    pub camera: Entity,
+    /// The mouse button that was released.
+    pub button: MouseButton,
+}
+
+/// Event sent when a mouse button is pressed and then released over the same entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct CursorClick {
+    /// The entity receiving this event, which is either the target or one of its ancestors.
+    pub listener: Entity,
+    /// The entity that was clicked.
+    pub target: Entity,
+    /// The camera that the entity is being viewed through.
+    pub camera: Entity,
+    /// The mouse button that was clicked.
+    pub button: MouseButton,
+}
+
+/// The state of the pointer for a single camera.
+#[derive(Default)]
+struct PointerState {
+    target: Option<Entity>,
+    /// The entity that each button was pressed on.
+    pressed: HashMap<MouseButton, Entity>,
+}
+
+#[derive(SystemParam)]
+struct PickableHierarchy<'w, 's> {
+    pickables: Query<'w, 's, &'static Pickable>,
+    parents: Query<'w, 's, &'static Parent>,
+}
+
+impl PickableHierarchy<'_, '_> {
+    /// Calls `f` for the target and each of its [`Pickable`] ancestors, until propagation is stopped.
+    fn bubble(&self, target: Entity, mut f: impl FnMut(Entity)) {
+        let mut current = Some(target);
+        while let Some(entity) = current {
+            if let Ok(pickable) = self.pickables.get(entity) {
+                f(entity);
+                if pickable.stop_propagation {
+                    break;
+                }
+            }
+            current = self.parents.get(entity).ok().map(|parent| parent.get());
+        }
+    }
+}
+
+#[derive(SystemParam)]
+struct PointerEventWriters<'w> {
+    enter: EventWriter<'w, CursorEnter>,
+    exit: EventWriter<'w, CursorExit>,
+    down: EventWriter<'w, CursorDown>,
+    up: EventWriter<'w, CursorUp>,
+    click: EventWriter<'w, CursorClick>,
+}
+
+fn send_pointer_events(
+    cameras: Query<(Entity, &Camera, &MousePos, &HoveredEntities)>,
+    hierarchy: PickableHierarchy,
+    buttons: Res<Input<MouseButton>>,
+    windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut states: Local<HashMap<Entity, PointerState>>,
+    mut events: PointerEventWriters,
+) {
+    states.retain(|&camera, _| cameras.contains(camera));
+
+    // Overlapping cameras would otherwise send events for the same click.
+    let active = camera_under_cursor(
+        cameras
+            .iter()
+            .map(|(entity, camera, pos, _)| (entity, camera, pos)),
+        &windows,
+        primary_window.get_single().ok(),
+    );
+
+    for (camera, .., hovered) in &cameras {
+        let state = states.entry(camera).or_default();
+        let is_active = active == Some(camera);
+
+        // The frontmost entity is the target even if it isn't `Pickable`, so that its ancestors
+        // still get events, and so that it blocks the entities behind it.
+        let target = hovered.top().filter(|_| is_active);
+        if target != state.target {
+            if let Some(target) = state.target {
+                hierarchy.bubble(target, |listener| {
+                    events.exit.send(CursorExit {
+                        listener,
+                        target,
+                        camera,
+                    });
+                });
+            }
+            if let Some(target) = target {
+                hierarchy.bubble(target, |listener| {
+                    events.enter.send(CursorEnter {
+                        listener,
+                        target,
+                        camera,
+                    });
+                });
+            }
+            state.target = target;
+        }
+
+        if !is_active {
+            state.pressed.clear();
+            continue;
+        }
+
+        for &button in buttons.get_just_pressed() {
+            state.pressed.remove(&button);
+            let Some(target) = target else {
+                continue;
+            };
+            state.pressed.insert(button, target);
+            hierarchy.bubble(target, |listener| {
+                events.down.send(CursorDown {
+                    listener,
+                    target,
+                    camera,
+                    button,
+                });
+            });
+        }
+
+        for &button in buttons.get_just_released() {
+            let pressed = state.pressed.remove(&button);
+            let Some(target) = target else {
+                continue;
+            };
+            hierarchy.bubble(target, |listener| {
+                events.up.send(CursorUp {
+                    listener,
+                    target,
+                    camera,
+                    button,
+                });
+            });
+            if pressed == Some(target) {
+                hierarchy.bubble(target, |listener| {
+                    events.click.send(CursorClick {
+                        listener,
+                        target,
+                        camera,
+                        button,
+                    });
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,414 @@
+//! Selecting entities by dragging the mouse around them, as in RTS games and editors.
+//!
+//! After adding [`SelectionPlugin`], dragging the mouse over a camera that tracks the world-space
+//! mouse position will select every [`Selectable`] entity inside of the dragged area, by adding a
+//! [`Selected`] component to them. The shape of the area depends on the [`SelectionTool`] resource:
+//!
+//! * With [`SelectionTool::Box`], a box is dragged from corner to corner. An entity is inside of the box
+//!   if its origin is, or if any part of its bounds overlaps the box. Bounds are found the same way
+//!   as in the [`picking`](crate::picking) module. While dragging, the camera has a [`BoxSelection`] component.
+//! * With [`SelectionTool::Lasso`], a free-form path is drawn around the entities, and an entity is
+//!   inside of it if its origin is. While dragging, the camera has a [`LassoSelection`] component.
+//!
+//! These components can be used to draw the selection area.
+//!
+//! By default, a new selection replaces the previous one. Holding shift adds to the previous selection,
+//! and holding control removes from it.
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_mouse_tracking_plugin::{Selectable, Selected};
+//!
+//! fn spawn_unit(mut commands: Commands) {
+//!     commands.spawn((SpatialBundle::default(), Selectable));
+//! }
+//!
+//! fn move_selected(mut units: Query<&mut Transform, With<Selected>>) {
+//!     for mut transform in &mut units {
+//!         transform.translation.x += 1.0;
+//!     }
+//! }
+//! # bevy::ecs::system::assert_is_system(spawn_unit);
+//! # bevy::ecs::system::assert_is_system(move_selected);
+//! ```
+
+use bevy::{
+    ecs::system::SystemParam, prelude::*, render::primitives::Aabb, transform::TransformSystem,
+    utils::HashSet, window::PrimaryWindow,
+};
+
+use crate::{
+    mouse_pos::MouseTrackingSet,
+    picking::{
+        camera_under_cursor, local_bounds, polygon_contains, PickShape, PickingPlugin, PickingSet,
+        SpriteBounds,
+    },
+    MousePos, MousePosWorld,
+};
+
+/// Plugin for selecting [`Selectable`] entities with the mouse.
+///
+/// This adds [`PickingPlugin`] if it hasn't been added already.
+pub struct SelectionPlugin {
+    /// The mouse button to drag with.
+    pub button: MouseButton,
+    /// Holding any of these keys adds to the previous selection.
+    pub additive_keys: Vec<KeyCode>,
+    /// Holding any of these keys removes from the previous selection.
+    pub subtractive_keys: Vec<KeyCode>,
+    /// How far a lasso path can stray when it gets simplified, in world units.
+    /// See [`simplify_path`].
+    pub lasso_tolerance: f32,
+}
+
+impl Default for SelectionPlugin {
+    fn default() -> Self {
+        Self {
+            button: MouseButton::Left,
+            additive_keys: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            subtractive_keys: vec![KeyCode::ControlLeft, KeyCode::ControlRight],
+            lasso_tolerance: 1.0,
+        }
+    }
+}
+
+/// The input settings for [`SelectionPlugin`].
+#[derive(Resource)]
+struct SelectionSettings {
+    button: MouseButton,
+    additive_keys: Vec<KeyCode>,
+    subtractive_keys: Vec<KeyCode>,
+    lasso_tolerance: f32,
+}
+
+#[derive(SystemParam)]
+struct SelectionInput<'w> {
+    settings: Res<'w, SelectionSettings>,
+    buttons: Res<'w, Input<MouseButton>>,
+    keys: Res<'w, Input<KeyCode>>,
+}
+
+impl Plugin for SelectionPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<PickingPlugin>() {
+            app.add_plugins(PickingPlugin);
+        }
+        app.insert_resource(SelectionSettings {
+            button: self.button,
+            additive_keys: self.additive_keys.clone(),
+            subtractive_keys: self.subtractive_keys.clone(),
+            lasso_tolerance: self.lasso_tolerance,
+        });
+        app.init_resource::<SelectionTool>();
+        app.add_systems(
+            PostUpdate,
+            (
+                start_selection,
+                (update_box_selection, update_lasso_selection),
+            )
+                .chain()
+                .after(TransformSystem::TransformPropagate)
+                .after(MouseTrackingSet::Resources)
+                .after(PickingSet),
+        );
+    }
+}
+
+/// Resource that determines the shape of new selections. This can be changed at any time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum SelectionTool {
+    /// Select entities by dragging a box around them.
+    #[default]
+    Box,
+    /// Select entities by drawing a free-form path around them.
+    Lasso,
+}
+
+/// Marker component for entities that can be selected.
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub struct Selectable;
+
+/// Marker component for entities that are currently selected.
+///
+/// This is managed by [`SelectionPlugin`], but it can also be added or removed manually.
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub struct Selected;
+
+/// How a new selection gets combined with the previous one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// The new selection replaces the previous one.
+    #[default]
+    Replace,
+    /// The new selection is added to the previous one.
+    Add,
+    /// The new selection is removed from the previous one.
+    Subtract,
+}
+
+impl SelectionMode {
+    fn from_keys(settings: &SelectionSettings, keys: &Input<KeyCode>) -> Self {
+        if keys.any_pressed(settings.subtractive_keys.iter().copied()) {
+            Self::Subtract
+        } else if keys.any_pressed(settings.additive_keys.iter().copied()) {
+            Self::Add
+        } else {
+            Self::Replace
+        }
+    }
+}
+
+/// Component that exists on a camera while a box is being dragged with it.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct BoxSelection {
+    /// The world-space position where the drag started.
+    pub start: Vec3,
+    /// The current world-space position of the mouse.
+    pub end: Vec3,
+    /// How the box gets combined with the previous selection.
+    pub mode: SelectionMode,
+}
+
+impl BoxSelection {
+    /// The world-space corners of the box, given the camera's transform.
+    ///
+    /// The box is aligned with the camera, so it becomes an oriented quad when the camera is rotated.
+    pub fn corners(&self, camera_transform: &GlobalTransform) -> [Vec2; 4] {
+        let to_local = camera_transform.affine().inverse();
+        let start = to_local.transform_point3(self.start);
+        let end = to_local.transform_point3(self.end);
+        let (min, max) = (start.min(end), start.max(end));
+        [
+            Vec3::new(min.x, min.y, start.z),
+            Vec3::new(max.x, min.y, start.z),
+            Vec3::new(max.x, max.y, start.z),
+            Vec3::new(min.x, max.y, start.z),
+        ]
+        .map(|corner| camera_transform.transform_point(corner).truncate())
+    }
+}
+
+/// Component that exists on a camera while a lasso is being drawn with it.
+#[derive(Debug, Clone, PartialEq, Component)]
+pub struct LassoSelection {
+    /// The world-space path of the mouse so far.
+    pub path: Vec<Vec2>,
+    /// How the lasso gets combined with the previous selection.
+    pub mode: SelectionMode,
+}
+
+/// Simplifies a path using the Ramer–Douglas–Peucker algorithm.
+///
+/// Points are removed as long as the simplified path stays within `tolerance` of the original.
+/// The first and last points are always kept.
+pub fn simplify_path(points: &[Vec2], tolerance: f32) -> Vec<Vec2> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let last = points.len() - 1;
+    let mut keep = vec![false; points.len()];
+    (keep[0], keep[last]) = (true, true);
+    let mut stack = vec![(0, last)];
+    while let Some((start, end)) = stack.pop() {
+        let (a, b) = (points[start], points[end]);
+        let farthest = (start + 1..end)
+            .map(|i| (i, segment_distance(points[i], a, b)))
+            .max_by(|(_, x), (_, y)| x.total_cmp(y));
+        if let Some((i, distance)) = farthest {
+            if distance > tolerance {
+                keep[i] = true;
+                stack.extend([(start, i), (i, end)]);
+            }
+        }
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&point, keep)| keep.then_some(point))
+        .collect()
+}
+
+/// The distance from a point to the line segment between `a` and `b`.
+fn segment_distance(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = if ab == Vec2::ZERO {
+        0.0
+    } else {
+        ((point - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+    };
+    point.distance(a + ab * t)
+}
+
+/// Checks whether two convex polygons overlap, using the separating axis theorem.
+fn convex_overlap(a: &[Vec2], b: &[Vec2]) -> bool {
+    fn edges(poly: &[Vec2]) -> impl Iterator<Item = Vec2> + '_ {
+        (0..poly.len()).map(move |i| poly[(i + 1) % poly.len()] - poly[i])
+    }
+    let project = |poly: &[Vec2], axis: Vec2| {
+        poly.iter()
+            .map(|p| p.dot(axis))
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), d| {
+                (min.min(d), max.max(d))
+            })
+    };
+    edges(a).chain(edges(b)).all(|edge| {
+        let axis = edge.perp();
+        let (a_min, a_max) = project(a, axis);
+        let (b_min, b_max) = project(b, axis);
+        a_min <= b_max && b_min <= a_max
+    })
+}
+
+/// Checks whether an entity's origin or bounds are inside of a world-space box.
+fn is_in_box(corners: &[Vec2; 4], transform: &GlobalTransform, bounds: Option<Rect>) -> bool {
+    if polygon_contains(corners, transform.translation().truncate()) {
+        return true;
+    }
+    bounds.is_some_and(|rect| {
+        let bounds = [
+            rect.min,
+            Vec2::new(rect.max.x, rect.min.y),
+            rect.max,
+            Vec2::new(rect.min.x, rect.max.y),
+        ]
+        .map(|corner| transform.transform_point(corner.extend(0.0)).truncate());
+        convex_overlap(corners, &bounds)
+    })
+}
+
+/// Adds and removes [`Selected`] components according to the mode.
+fn apply_selection(
+    commands: &mut Commands,
+    mode: SelectionMode,
+    inside: &HashSet<Entity>,
+    selectables: impl Iterator<Item = (Entity, bool)>,
+) {
+    for (entity, selected) in selectables {
+        let select = match mode {
+            SelectionMode::Replace => inside.contains(&entity),
+            SelectionMode::Add => selected || inside.contains(&entity),
+            SelectionMode::Subtract => selected && !inside.contains(&entity),
+        };
+        if select && !selected {
+            commands.entity(entity).insert(Selected);
+        } else if !select && selected {
+            commands.entity(entity).remove::<Selected>();
+        }
+    }
+}
+
+fn start_selection(
+    mut commands: Commands,
+    input: SelectionInput,
+    tool: Res<SelectionTool>,
+    cameras: Query<(Entity, &Camera, &MousePos, &MousePosWorld)>,
+    windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    if !input.buttons.just_pressed(input.settings.button) {
+        return;
+    }
+    let camera = camera_under_cursor(
+        cameras
+            .iter()
+            .map(|(entity, camera, pos, _)| (entity, camera, pos)),
+        &windows,
+        primary_window.get_single().ok(),
+    );
+    if let Some(camera) = camera {
+        let (.., &mouse_pos_world) = cameras.get(camera).unwrap();
+        let mode = SelectionMode::from_keys(&input.settings, &input.keys);
+        match *tool {
+            SelectionTool::Box => commands.entity(camera).insert(BoxSelection {
+                start: *mouse_pos_world,
+                end: *mouse_pos_world,
+                mode,
+            }),
+            SelectionTool::Lasso => commands.entity(camera).insert(LassoSelection {
+                path: vec![mouse_pos_world.truncate()],
+                mode,
+            }),
+        };
+    }
+}
+
+fn update_box_selection(
+    mut commands: Commands,
+    settings: Res<SelectionSettings>,
+    buttons: Res<Input<MouseButton>>,
+    mut cameras: Query<(Entity, &mut BoxSelection, &MousePosWorld, &GlobalTransform)>,
+    selectables: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            Has<Selected>,
+            Option<&PickShape>,
+            Option<&SpriteBounds>,
+            Option<&Aabb>,
+        ),
+        With<Selectable>,
+    >,
+) {
+    for (camera, mut selection, &mouse_pos_world, camera_transform) in &mut cameras {
+        if selection.end != *mouse_pos_world {
+            selection.end = *mouse_pos_world;
+        }
+        if buttons.pressed(settings.button) {
+            continue;
+        }
+
+        let corners = selection.corners(camera_transform);
+        let inside = selectables
+            .iter()
+            .filter(|&(_, transform, _, shape, sprite, aabb)| {
+                is_in_box(&corners, transform, local_bounds(shape, sprite, aabb))
+            })
+            .map(|(entity, ..)| entity)
+            .collect();
+        apply_selection(
+            &mut commands,
+            selection.mode,
+            &inside,
+            selectables
+                .iter()
+                .map(|(entity, _, selected, ..)| (entity, selected)),
+        );
+        commands.entity(camera).remove::<BoxSelection>();
+    }
+}
+
+fn update_lasso_selection(
+    mut commands: Commands,
+    settings: Res<SelectionSettings>,
+    buttons: Res<Input<MouseButton>>,
+    mut cameras: Query<(Entity, &mut LassoSelection, &MousePosWorld)>,
+    selectables: Query<(Entity, &GlobalTransform, Has<Selected>), With<Selectable>>,
+) {
+    for (camera, mut selection, &mouse_pos_world) in &mut cameras {
+        let position = mouse_pos_world.truncate();
+        if selection.path.last() != Some(&position) {
+            selection.path.push(position);
+        }
+        if buttons.pressed(settings.button) {
+            continue;
+        }
+
+        let polygon = simplify_path(&selection.path, settings.lasso_tolerance);
+        let inside = selectables
+            .iter()
+            .filter(|(_, transform, _)| {
+                polygon_contains(&polygon, transform.translation().truncate())
+            })
+            .map(|(entity, ..)| entity)
+            .collect();
+        apply_selection(
+            &mut commands,
+            selection.mode,
+            &inside,
+            selectables
+                .iter()
+                .map(|(entity, _, selected)| (entity, selected)),
+        );
+        commands.entity(camera).remove::<LassoSelection>();
+    }
+}
@@ -0,0 +1,113 @@
+//! Quantization of the world-space mouse position, for pixel-art and tile-based games.
+//!
+//! Adding [`PixelSnapping`] to a camera makes its [`MousePosWorld`] snap to the grid of pixels
+//! that the camera renders, and [`InitTileTracking`] adds a [`MouseTile`] component that tracks
+//! which tile the mouse is hovering over.
+
+use std::{fmt::Display, ops::Deref};
+
+use bevy::{ecs::system::EntityCommand, prelude::*};
+
+use crate::{mouse_pos::InitWorldTracking, MousePosWorld};
+
+/// How a position gets rounded onto a grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SnapRounding {
+    /// Snap to the corner of the grid cell that contains the position.
+    #[default]
+    Floor,
+    /// Snap to the nearest corner of the grid.
+    Round,
+}
+
+impl SnapRounding {
+    fn apply(self, pos: Vec2) -> Vec2 {
+        match self {
+            Self::Floor => pos.floor(),
+            Self::Round => pos.round(),
+        }
+    }
+
+    /// Snaps the position onto a grid with the specified cell size.
+    pub fn snap(self, pos: Vec2, cell_size: Vec2) -> Vec2 {
+        self.apply(pos / cell_size) * cell_size
+    }
+}
+
+/// Component that makes a camera's [`MousePosWorld`] snap to the world-space grid of pixels rendered by the camera.
+///
+/// The size of a pixel is derived from the camera's [`OrthographicProjection`] and [`Transform::scale`],
+/// so for the default scaling mode each pixel is [`OrthographicProjection::scale`] world units wide.
+/// The grid starts at the world origin, so it stays put as the camera moves.
+///
+/// A rotated grid of pixels can't line up with the world axes, so if the camera is rotated,
+/// the grid is aligned with the camera and starts at the camera's position instead.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Component)]
+pub struct PixelSnapping(pub SnapRounding);
+
+/// The tile of a grid that the mouse is currently over, in world-space.
+///
+/// Tile `(0, 0)` spans from the world origin to `tile_size`.
+/// This can be accessed as either a component or a resource, just like [`MousePosWorld`].
+#[derive(Debug, Default, Resource, Clone, Copy, PartialEq, Eq, Component)]
+pub struct MouseTile(IVec2);
+
+impl Deref for MouseTile {
+    type Target = IVec2;
+
+    fn deref(&self) -> &IVec2 {
+        &self.0
+    }
+}
+
+impl Display for MouseTile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// The size of the tiles tracked by [`MouseTile`], in world units.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct TileGrid {
+    /// The width and height of each tile.
+    pub tile_size: Vec2,
+}
+
+/// An [`EntityCommand`] that adds the components [`TileGrid`] and [`MouseTile`] to a camera, with a correct initial position.
+///
+/// Executing this command automatically executes [`InitWorldTracking`].
+pub struct InitTileTracking {
+    /// The width and height of each tile, in world units.
+    pub tile_size: Vec2,
+}
+
+impl EntityCommand for InitTileTracking {
+    fn apply(self, entity: Entity, world: &mut World) {
+        InitWorldTracking.apply(entity, world);
+
+        let mut entity_mut = world.entity_mut(entity);
+        let world_pos = entity_mut.get::<MousePosWorld>().unwrap();
+        let tile = compute_tile(**world_pos, self.tile_size);
+        entity_mut.insert((
+            TileGrid {
+                tile_size: self.tile_size,
+            },
+            MouseTile(tile),
+        ));
+    }
+}
+
+fn compute_tile(world_pos: Vec3, tile_size: Vec2) -> IVec2 {
+    (world_pos.truncate() / tile_size).floor().as_ivec2()
+}
+
+pub(crate) fn update_tile(
+    mut tracking: Query<
+        (&MousePosWorld, &TileGrid, &mut MouseTile),
+        Or<(Changed<MousePosWorld>, Changed<TileGrid>)>,
+    >,
+) {
+    for (world_pos, grid, mut tile) in tracking.iter_mut() {
+        tile.set_if_neq(MouseTile(compute_tile(**world_pos, grid.tile_size)));
+    }
+}
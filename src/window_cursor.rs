@@ -0,0 +1,194 @@
+//! Tracking of the cursor on a per-window basis, independent of any cameras.
+//!
+//! Every [`Window`] entity automatically gets a [`WindowCursor`] component,
+//! and the cursor of the window with focus is available in the [`FocusedWindowCursor`] resource.
+//!
+//! When there are several windows, the [`DesktopCursorPos`] resource tracks the cursor in a coordinate
+//! space shared by all windows, which is useful for dragging things from one window to another.
+//!
+//! ```
+//! use bevy::{prelude::*, window::{CursorMoved, WindowPosition, WindowResolution}};
+//! use bevy_mouse_tracking_plugin::{prelude::*, DesktopCursorPos};
+//!
+//! let mut app = App::new();
+//! app.add_plugins((MinimalPlugins, WindowPlugin::default(), MousePosPlugin::default()));
+//!
+//! // Open a second window, with a scale factor of 2.
+//! let window = app
+//!     .world
+//!     .spawn(Window {
+//!         position: WindowPosition::At(IVec2::new(1000, 200)),
+//!         resolution: WindowResolution::default().with_scale_factor_override(2.0),
+//!         ..default()
+//!     })
+//!     .id();
+//! app.update();
+//!
+//! // Move the cursor into the new window.
+//! let position = Vec2::new(10.0, 20.0);
+//! app.world.get_mut::<Window>(window).unwrap().set_cursor_position(Some(position));
+//! app.world.send_event(CursorMoved { window, position });
+//! app.update();
+//!
+//! let desktop = app.world.resource::<DesktopCursorPos>();
+//! assert_eq!(desktop.window(), Some(window));
+//! assert_eq!(desktop.position(), Vec2::new(1020.0, 240.0));
+//! ```
+
+use std::{ops::Deref, time::Duration};
+
+use bevy::{prelude::*, window::WindowPosition};
+
+/// The state of the cursor within a single window.
+///
+/// This is added to all [`Window`] entities automatically by [`MousePosPlugin`](crate::mouse_pos::MousePosPlugin),
+/// and gets updated during [`MouseTrackingSet::Screen`](crate::mouse_pos::MouseTrackingSet::Screen).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Component)]
+pub struct WindowCursor {
+    position: Vec2,
+    present: bool,
+    last_moved: Duration,
+}
+
+impl WindowCursor {
+    /// The position of the cursor in logical pixels, relative to the top left of the window.
+    /// If the cursor is not in the window, this is the last position it was seen at.
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Whether or not the cursor is currently inside of the window.
+    pub fn is_present(&self) -> bool {
+        self.present
+    }
+
+    /// The value of [`Time::elapsed`] the last time that the cursor moved within the window.
+    pub fn last_moved(&self) -> Duration {
+        self.last_moved
+    }
+}
+
+/// The [`WindowCursor`] of the window that currently has focus.
+/// If no window has focus, this will be the default value.
+#[derive(Debug, Default, Resource, Clone, Copy, PartialEq)]
+pub struct FocusedWindowCursor {
+    window: Option<Entity>,
+    cursor: WindowCursor,
+}
+
+impl FocusedWindowCursor {
+    /// The window that currently has focus, if any.
+    pub fn window(&self) -> Option<Entity> {
+        self.window
+    }
+}
+
+impl Deref for FocusedWindowCursor {
+    type Target = WindowCursor;
+
+    fn deref(&self) -> &WindowCursor {
+        &self.cursor
+    }
+}
+
+pub(crate) fn update_window_cursors(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut movement: EventReader<CursorMoved>,
+    new_windows: Query<(Entity, &Window), Without<WindowCursor>>,
+    mut windows: Query<(&Window, &mut WindowCursor)>,
+) {
+    for (entity, window) in &new_windows {
+        let position = window.cursor_position();
+        commands.entity(entity).insert(WindowCursor {
+            position: position.unwrap_or_default(),
+            present: position.is_some(),
+            last_moved: time.elapsed(),
+        });
+    }
+
+    for &CursorMoved {
+        window, position, ..
+    } in movement.read()
+    {
+        if let Ok((_, mut cursor)) = windows.get_mut(window) {
+            cursor.position = position;
+            cursor.present = true;
+            cursor.last_moved = time.elapsed();
+        }
+    }
+
+    // The window keeps track of whether the cursor has left.
+    for (window, mut cursor) in windows.iter_mut() {
+        if cursor.present && window.cursor_position().is_none() {
+            cursor.present = false;
+        }
+    }
+}
+
+pub(crate) fn update_focused_window_cursor(
+    mut focused: ResMut<FocusedWindowCursor>,
+    windows: Query<(Entity, &Window, &WindowCursor)>,
+) {
+    let value = windows
+        .iter()
+        .find(|(_, window, _)| window.focused)
+        .map_or_else(default, |(entity, _, &cursor)| FocusedWindowCursor {
+            window: Some(entity),
+            cursor,
+        });
+    focused.set_if_neq(value);
+}
+
+/// The position of the cursor in desktop-space, which is shared between all windows.
+///
+/// This is measured in physical pixels, with the origin at the top left of the screen.
+/// Windows whose [`Window::position`] is not yet known are ignored.
+#[derive(Debug, Default, Resource, Clone, Copy, PartialEq)]
+pub struct DesktopCursorPos {
+    position: Vec2,
+    window: Option<Entity>,
+}
+
+impl DesktopCursorPos {
+    /// The position of the cursor in desktop-space.
+    /// If the cursor is not over any window, this is the last position it was seen at.
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// The window that the cursor is currently over, if any.
+    pub fn window(&self) -> Option<Entity> {
+        self.window
+    }
+}
+
+pub(crate) fn update_desktop_cursor(
+    mut desktop: ResMut<DesktopCursorPos>,
+    windows: Query<(Entity, &Window, &WindowCursor)>,
+) {
+    // If the cursor is somehow in more than one window, the one it moved in most recently wins.
+    let hovered = windows
+        .iter()
+        .filter(|(_, _, cursor)| cursor.present)
+        .filter_map(|(entity, window, cursor)| {
+            let WindowPosition::At(origin) = window.position else {
+                return None;
+            };
+            let position = origin.as_vec2() + cursor.position * window.scale_factor() as f32;
+            Some((entity, position, cursor.last_moved))
+        })
+        .max_by_key(|&(.., last_moved)| last_moved);
+
+    let value = match hovered {
+        Some((entity, position, _)) => DesktopCursorPos {
+            position,
+            window: Some(entity),
+        },
+        None => DesktopCursorPos {
+            position: desktop.position,
+            window: None,
+        },
+    };
+    desktop.set_if_neq(value);
+}
@@ -0,0 +1,103 @@
+use bevy::{
+    ecs::system::EntityCommand,
+    input::{mouse::MouseMotion, InputPlugin},
+    prelude::*,
+};
+use bevy_mouse_tracking_plugin::{prelude::*, MainCamera};
+
+#[derive(Resource, Default, PartialEq, Debug)]
+struct Runs {
+    cursor: u32,
+    world: u32,
+    motion: u32,
+}
+
+/// Builds a headless app with a main camera, and systems that count how often each condition passes.
+fn setup() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        InputPlugin,
+        WindowPlugin::default(),
+        MousePosPlugin::default(),
+        MouseMotionPlugin::default(),
+    ))
+    .init_resource::<Runs>()
+    .add_systems(
+        PostUpdate,
+        (
+            (|mut runs: ResMut<Runs>| runs.cursor += 1).run_if(cursor_moved()),
+            (|mut runs: ResMut<Runs>| runs.world += 1).run_if(cursor_world_moved()),
+            (|mut runs: ResMut<Runs>| runs.motion += 1).run_if(mouse_motion_nonzero()),
+        ),
+    );
+
+    let camera = app
+        .world
+        .spawn((Camera2dBundle::default(), MainCamera))
+        .id();
+    app.update();
+    InitWorldTracking.apply(camera, &mut app.world);
+    app.update();
+    app.update();
+    app.world.insert_resource(Runs::default());
+    (app, camera)
+}
+
+#[test]
+fn cursor_world_moved_by_camera() {
+    let (mut app, camera) = setup();
+
+    // Nothing moved, so nothing runs.
+    app.update();
+    assert_eq!(*app.world.resource::<Runs>(), Runs::default());
+
+    // Moving the camera changes the world-space position, but not the screen-space position.
+    // Tracking uses the camera's `GlobalTransform`, which gets propagated at the end of the frame.
+    app.world
+        .get_mut::<Transform>(camera)
+        .unwrap()
+        .translation
+        .x += 10.0;
+    app.update();
+    app.update();
+    let runs = Runs {
+        world: 1,
+        ..default()
+    };
+    assert_eq!(*app.world.resource::<Runs>(), runs);
+
+    // The camera stopped, so the condition stops passing.
+    app.update();
+    assert_eq!(*app.world.resource::<Runs>(), runs);
+}
+
+#[test]
+fn mouse_motion_nonzero_once() {
+    let (mut app, _) = setup();
+
+    app.world.send_event(MouseMotion {
+        delta: Vec2::new(3.0, -4.0),
+    });
+    app.update();
+    let runs = Runs {
+        motion: 1,
+        ..default()
+    };
+    assert_eq!(*app.world.resource::<Runs>(), runs);
+
+    // Motion only lasts for a single frame.
+    app.update();
+    assert_eq!(*app.world.resource::<Runs>(), runs);
+
+    // Motion that cancels out doesn't count.
+    app.world.send_event(MouseMotion {
+        delta: Vec2::new(3.0, 0.0),
+    });
+    app.world.send_event(MouseMotion {
+        delta: Vec2::new(-3.0, 0.0),
+    });
+    app.update();
+    assert_eq!(*app.world.resource::<Runs>(), runs);
+}
@@ -0,0 +1,164 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    ecs::system::EntityCommand,
+    input::{mouse::MouseButtonInput, ButtonState, InputPlugin},
+    prelude::*,
+    window::PrimaryWindow,
+};
+use bevy_mouse_tracking_plugin::{
+    gestures::{GestureRecognized, GestureTemplates},
+    prelude::*,
+};
+
+/// The default value of `GesturePlugin::min_score`.
+const MIN_SCORE: f32 = 0.8;
+
+fn builtin_templates() -> GestureTemplates {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, MousePosPlugin::default()))
+        .add_plugins(GesturePlugin::default());
+    app.world.resource::<GestureTemplates>().clone()
+}
+
+/// Draws a wobbly circle in window coordinates, starting from the top.
+fn circle(center: Vec2, radius: f32, clockwise: bool) -> Vec<Vec2> {
+    let dir = if clockwise { 1.0 } else { -1.0 };
+    (0..=50)
+        .map(|i| {
+            let t = i as f32 / 50.0 * TAU;
+            let r = radius * (1.0 + 0.05 * (t * 5.0).sin());
+            center + Vec2::new(dir * t.sin(), -t.cos()) * r
+        })
+        .collect()
+}
+
+/// Draws a slightly curved line in window coordinates.
+fn swipe(start: Vec2, end: Vec2) -> Vec<Vec2> {
+    let normal = (end - start).perp() * 0.05;
+    (0..=20)
+        .map(|i| {
+            let t = i as f32 / 20.0;
+            start.lerp(end, t) + normal * (t * (1.0 - t))
+        })
+        .collect()
+}
+
+fn assert_recognized(templates: &GestureTemplates, path: &[Vec2], expected: &str) {
+    let (name, score) = templates.recognize(path).unwrap();
+    assert_eq!(name, expected, "score: {score}");
+    assert!(score >= MIN_SCORE, "{name}: {score}");
+}
+
+#[test]
+fn recognize_builtins() {
+    let templates = builtin_templates();
+
+    assert_recognized(
+        &templates,
+        &circle(Vec2::new(300.0, 200.0), 80.0, true),
+        "circle",
+    );
+    assert_recognized(
+        &templates,
+        &circle(Vec2::new(50.0, 50.0), 20.0, false),
+        "circle",
+    );
+
+    let center = Vec2::new(400.0, 300.0);
+    assert_recognized(
+        &templates,
+        &swipe(center, center + Vec2::new(-200.0, 10.0)),
+        "swipe_left",
+    );
+    assert_recognized(
+        &templates,
+        &swipe(center, center + Vec2::new(200.0, -10.0)),
+        "swipe_right",
+    );
+    assert_recognized(
+        &templates,
+        &swipe(center, center + Vec2::new(10.0, -200.0)),
+        "swipe_up",
+    );
+    assert_recognized(
+        &templates,
+        &swipe(center, center + Vec2::new(-10.0, 200.0)),
+        "swipe_down",
+    );
+
+    let zigzag: Vec<_> = (0..=4)
+        .map(|i| Vec2::new(i as f32 * 40.0, if i % 2 == 0 { 100.0 } else { 140.0 }))
+        .collect();
+    assert_recognized(&templates, &zigzag, "zigzag");
+}
+
+#[test]
+fn reject_unknown_shapes() {
+    let templates = builtin_templates();
+    // A spiral doesn't look like any of the built-in gestures.
+    let spiral: Vec<_> = (0..=100)
+        .map(|i| {
+            let t = i as f32 / 100.0 * 3.0 * TAU;
+            Vec2::from_angle(t) * t * 10.0
+        })
+        .collect();
+    let (_, score) = templates.recognize(&spiral).unwrap();
+    assert!(score < MIN_SCORE, "{score}");
+
+    // A path with no length can't be recognized at all.
+    assert!(templates.recognize(&[Vec2::ONE, Vec2::ONE]).is_none());
+}
+
+#[test]
+fn recognized_event() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        WindowPlugin::default(),
+        MousePosPlugin::default(),
+    ))
+    .add_plugins(GesturePlugin::default());
+    let window = app
+        .world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(&app.world);
+    let camera = app.world.spawn(Camera2dBundle::default()).id();
+    InitMouseTracking.apply(camera, &mut app.world);
+
+    let move_cursor = |app: &mut App, position: Vec2| {
+        app.world
+            .get_mut::<Window>(window)
+            .unwrap()
+            .set_cursor_position(Some(position));
+        app.world.send_event(CursorMoved { window, position });
+        app.update();
+    };
+    let press = |app: &mut App, state: ButtonState| {
+        app.world.send_event(MouseButtonInput {
+            button: MouseButton::Right,
+            state,
+            window,
+        });
+        app.update();
+    };
+
+    let path = swipe(Vec2::new(600.0, 300.0), Vec2::new(300.0, 310.0));
+    move_cursor(&mut app, path[0]);
+    press(&mut app, ButtonState::Pressed);
+    for &point in &path[1..] {
+        move_cursor(&mut app, point);
+    }
+    press(&mut app, ButtonState::Released);
+
+    let events: Vec<_> = app
+        .world
+        .resource_mut::<Events<GestureRecognized>>()
+        .drain()
+        .collect();
+    assert_eq!(events.len(), 1, "{events:?}");
+    assert_eq!(events[0].name, "swipe_left");
+    assert_eq!(events[0].camera, camera);
+    assert!(events[0].score >= MIN_SCORE);
+}
@@ -0,0 +1,146 @@
+use bevy::{
+    ecs::system::EntityCommand,
+    input::{mouse::MouseButtonInput, ButtonState, InputPlugin},
+    prelude::*,
+    render::camera::{camera_system, ManualTextureViews},
+    window::{ExitCondition, PrimaryWindow, WindowResolution},
+};
+use bevy_mouse_tracking_plugin::{
+    prelude::*,
+    selection::{simplify_path, LassoSelection, SelectionTool},
+    Selectable, Selected,
+};
+
+#[test]
+fn simplify_zigzag() {
+    // A line with a bit of jitter gets straightened out, keeping only its ends.
+    let points: Vec<_> = (0..=10)
+        .map(|i| Vec2::new(i as f32, 0.05 * (i % 2) as f32))
+        .collect();
+    assert_eq!(
+        simplify_path(&points, 0.1),
+        [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)]
+    );
+
+    // A corner sticks out further than the tolerance, so it gets kept.
+    let points = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.05),
+        Vec2::new(4.0, 0.0),
+        Vec2::new(5.0, 3.0),
+        Vec2::new(6.0, 6.05),
+        Vec2::new(7.0, 9.0),
+    ];
+    assert_eq!(
+        simplify_path(&points, 0.1),
+        [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(7.0, 9.0)
+        ]
+    );
+
+    // Paths too short to simplify are left alone.
+    let points = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0)];
+    assert_eq!(simplify_path(&points, 10.0), points);
+}
+
+/// Builds a headless app with an 800x600 window and a camera centered on the world origin.
+fn setup() -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        TransformPlugin,
+        InputPlugin,
+        WindowPlugin {
+            primary_window: Some(Window {
+                resolution: WindowResolution::new(800.0, 600.0),
+                ..default()
+            }),
+            exit_condition: ExitCondition::DontExit,
+            close_when_requested: false,
+        },
+        MousePosPlugin::default(),
+        SelectionPlugin::default(),
+    ))
+    .init_asset::<Image>()
+    .init_resource::<ManualTextureViews>()
+    .add_systems(PostUpdate, camera_system::<OrthographicProjection>);
+
+    let window = app
+        .world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(&app.world);
+    app.world.send_event(bevy::window::WindowCreated { window });
+    let camera = app.world.spawn(Camera2dBundle::default()).id();
+    app.update();
+    InitWorldTracking.apply(camera, &mut app.world);
+    (app, window, camera)
+}
+
+/// Moves the cursor to a world-space position, and runs a frame.
+fn move_cursor(app: &mut App, window: Entity, world_pos: Vec2) {
+    let position = Vec2::new(400.0 + world_pos.x, 300.0 - world_pos.y);
+    app.world
+        .get_mut::<Window>(window)
+        .unwrap()
+        .set_cursor_position(Some(position));
+    app.world.send_event(CursorMoved { window, position });
+    app.update();
+}
+
+fn press(app: &mut App, window: Entity, state: ButtonState) {
+    app.world.send_event(MouseButtonInput {
+        button: MouseButton::Left,
+        state,
+        window,
+    });
+    app.update();
+}
+
+#[test]
+fn concave_lasso() {
+    let (mut app, window, camera) = setup();
+    *app.world.resource_mut::<SelectionTool>() = SelectionTool::Lasso;
+
+    let mut spawn_unit = |position: Vec2| {
+        app.world
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_translation(position.extend(0.0))),
+                Selectable,
+            ))
+            .id()
+    };
+    let in_arm = spawn_unit(Vec2::new(-40.0, 30.0));
+    let in_base = spawn_unit(Vec2::new(0.0, -30.0));
+    let in_notch = spawn_unit(Vec2::new(0.0, 30.0));
+    let outside = spawn_unit(Vec2::new(100.0, 0.0));
+
+    // A U shape, with the notch opening upwards.
+    let corners = [
+        Vec2::new(-50.0, -50.0),
+        Vec2::new(50.0, -50.0),
+        Vec2::new(50.0, 50.0),
+        Vec2::new(20.0, 50.0),
+        Vec2::new(20.0, 0.0),
+        Vec2::new(-20.0, 0.0),
+        Vec2::new(-20.0, 50.0),
+        Vec2::new(-50.0, 50.0),
+    ];
+    move_cursor(&mut app, window, corners[0]);
+    press(&mut app, window, ButtonState::Pressed);
+    for edge in corners.windows(2) {
+        for i in 1..=4 {
+            move_cursor(&mut app, window, edge[0].lerp(edge[1], i as f32 / 4.0));
+        }
+    }
+    assert!(app.world.get::<LassoSelection>(camera).is_some());
+    press(&mut app, window, ButtonState::Released);
+
+    assert!(app.world.get::<LassoSelection>(camera).is_none());
+    assert!(app.world.get::<Selected>(in_arm).is_some());
+    assert!(app.world.get::<Selected>(in_base).is_some());
+    assert!(app.world.get::<Selected>(in_notch).is_none());
+    assert!(app.world.get::<Selected>(outside).is_none());
+}
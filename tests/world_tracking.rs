@@ -0,0 +1,215 @@
+use bevy::{
+    ecs::system::EntityCommand,
+    prelude::*,
+    render::camera::{camera_system, ManualTextureViews, ScalingMode, Viewport},
+    window::{ExitCondition, PrimaryWindow, WindowResized, WindowResolution},
+};
+use bevy_mouse_tracking_plugin::{mouse_pos::InitWorldTracking, prelude::*, MousePosWorld};
+
+/// Builds a headless app with an 800x600 window and an orthographic camera that tracks the mouse.
+fn setup(
+    scale_factor: f64,
+    projection: OrthographicProjection,
+    transform: Transform,
+) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        TransformPlugin,
+        WindowPlugin {
+            primary_window: Some(Window {
+                resolution: WindowResolution::new(800.0, 600.0)
+                    .with_scale_factor_override(scale_factor),
+                ..default()
+            }),
+            exit_condition: ExitCondition::DontExit,
+            close_when_requested: false,
+        },
+        MousePosPlugin::default(),
+    ))
+    .init_asset::<Image>()
+    .init_resource::<ManualTextureViews>()
+    .add_systems(PostUpdate, camera_system::<OrthographicProjection>);
+
+    let window = window(&mut app);
+    app.world.send_event(bevy::window::WindowCreated { window });
+    let camera = app
+        .world
+        .spawn(Camera2dBundle {
+            projection,
+            transform,
+            ..default()
+        })
+        .id();
+    // Let bevy compute the camera's viewport and transform before tracking starts.
+    app.update();
+    InitWorldTracking.apply(camera, &mut app.world);
+    app.update();
+    (app, camera)
+}
+
+fn window(app: &mut App) -> Entity {
+    app.world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(&app.world)
+}
+
+fn move_cursor(app: &mut App, position: Vec2) {
+    let window = window(app);
+    app.world.send_event(CursorMoved { window, position });
+    app.update();
+}
+
+/// Asserts that projecting the world-space mouse position back onto the screen gives `expected`.
+fn assert_round_trip(app: &App, camera: Entity, expected: Vec2) {
+    let world_pos = **app.world.get::<MousePosWorld>(camera).unwrap();
+    let camera_transform = app.world.get::<GlobalTransform>(camera).unwrap();
+    let screen_pos = app
+        .world
+        .get::<Camera>(camera)
+        .unwrap()
+        .world_to_viewport(camera_transform, world_pos)
+        .unwrap();
+    assert!(
+        screen_pos.distance(expected) < 1e-2,
+        "{expected} -> {world_pos} -> {screen_pos}"
+    );
+}
+
+fn check_round_trip(scale_factor: f64, scaling_mode: ScalingMode, transform: Transform) {
+    let projection = OrthographicProjection {
+        scaling_mode,
+        ..default()
+    };
+    let (mut app, camera) = setup(scale_factor, projection, transform);
+    for position in [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(400.0, 300.0),
+        Vec2::new(123.0, 456.0),
+        Vec2::new(799.0, 1.0),
+    ] {
+        move_cursor(&mut app, position);
+        assert_round_trip(&app, camera, position);
+    }
+}
+
+#[test]
+fn window_size() {
+    for scale_factor in [1.0, 1.5, 2.0] {
+        check_round_trip(scale_factor, ScalingMode::WindowSize(1.0), default());
+        check_round_trip(scale_factor, ScalingMode::WindowSize(4.0), default());
+    }
+}
+
+#[test]
+fn fixed_vertical() {
+    check_round_trip(1.0, ScalingMode::FixedVertical(10.0), default());
+    check_round_trip(2.0, ScalingMode::FixedVertical(10.0), default());
+}
+
+#[test]
+fn auto_min() {
+    let mode = ScalingMode::AutoMin {
+        min_width: 16.0,
+        min_height: 9.0,
+    };
+    check_round_trip(1.0, mode.clone(), default());
+    check_round_trip(2.0, mode, default());
+}
+
+#[test]
+fn rotated_camera() {
+    let transform =
+        Transform::from_xyz(50.0, -20.0, 10.0).with_rotation(Quat::from_rotation_z(0.7));
+    check_round_trip(1.0, ScalingMode::WindowSize(1.0), transform);
+    check_round_trip(1.0, ScalingMode::FixedVertical(10.0), transform);
+}
+
+#[test]
+fn scaled_camera() {
+    let transform = Transform::from_scale(Vec3::new(2.0, 0.5, 1.0));
+    check_round_trip(1.0, ScalingMode::WindowSize(1.0), transform);
+    check_round_trip(
+        2.0,
+        ScalingMode::FixedVertical(10.0),
+        transform.with_rotation(Quat::from_rotation_z(-1.2)),
+    );
+}
+
+#[test]
+fn pixel_snapping() {
+    use bevy_mouse_tracking_plugin::{snapping::SnapRounding, PixelSnapping};
+
+    let transform = Transform::from_xyz(0.3, -0.6, 0.0).with_scale(Vec3::splat(2.0));
+    let (mut app, camera) = setup(1.0, default(), transform);
+    app.world
+        .entity_mut(camera)
+        .insert(PixelSnapping(SnapRounding::Floor));
+    move_cursor(&mut app, Vec2::new(123.4, 456.7));
+    // Each pixel is two world units wide, and the grid is aligned with the world origin.
+    let world_pos = **app.world.get::<MousePosWorld>(camera).unwrap();
+    assert_eq!(world_pos.truncate() % 2.0, Vec2::ZERO, "{world_pos}");
+}
+
+/// Moves the cursor, and returns the resulting world-space position.
+fn track(app: &mut App, camera: Entity) -> Vec3 {
+    move_cursor(app, Vec2::new(100.0, 100.0));
+    **app.world.get::<MousePosWorld>(camera).unwrap()
+}
+
+/// Runs a frame for bevy to update the camera, and a frame for the tracking to catch up.
+fn settle(app: &mut App) {
+    app.update();
+    app.update();
+}
+
+#[test]
+fn projection_changed() {
+    let (mut app, camera) = setup(1.0, default(), default());
+    let before = track(&mut app, camera);
+    app.world
+        .get_mut::<OrthographicProjection>(camera)
+        .unwrap()
+        .scale = 2.0;
+    settle(&mut app);
+    let after = **app.world.get::<MousePosWorld>(camera).unwrap();
+    assert_eq!(after, before * 2.0);
+    assert_round_trip(&app, camera, Vec2::new(100.0, 100.0));
+}
+
+#[test]
+fn viewport_changed() {
+    let (mut app, camera) = setup(1.0, default(), default());
+    let before = track(&mut app, camera);
+    app.world.get_mut::<Camera>(camera).unwrap().viewport = Some(Viewport {
+        physical_position: UVec2::new(50, 50),
+        physical_size: UVec2::new(400, 300),
+        ..default()
+    });
+    settle(&mut app);
+    let after = **app.world.get::<MousePosWorld>(camera).unwrap();
+    assert_ne!(after, before);
+    assert_round_trip(&app, camera, Vec2::new(50.0, 50.0));
+}
+
+#[test]
+fn window_resized() {
+    let (mut app, camera) = setup(1.0, default(), default());
+    let before = track(&mut app, camera);
+    let window = window(&mut app);
+    app.world
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set(1000.0, 800.0);
+    app.world.send_event(WindowResized {
+        window,
+        width: 1000.0,
+        height: 800.0,
+    });
+    settle(&mut app);
+    let after = **app.world.get::<MousePosWorld>(camera).unwrap();
+    assert_eq!(after, before + Vec3::new(-100.0, 100.0, 0.0));
+    assert_round_trip(&app, camera, Vec2::new(100.0, 100.0));
+}